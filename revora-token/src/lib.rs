@@ -0,0 +1,291 @@
+#![no_std]
+#![deny(unsafe_code)]
+#![deny(clippy::dbg_macro, clippy::todo, clippy::unimplemented)]
+//! SEP-41 offering token for `RevoraRevenueShare`.
+//!
+//! Every transfer, mint and burn is reported to the revenue contract through
+//! `on_token_transfer`, which settles the accrued revenue of both sides and moves
+//! their share with the balance. The offering must have called `enable_token_hooks`
+//! before the first mint.
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token::TokenInterface, Address, Env, String,
+};
+
+/// Token error codes. `TokenInterface` methods panic with these; the others return them.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(u32)]
+pub enum TokenError {
+    /// `initialize` was already called.
+    AlreadyInitialized = 1,
+    /// `initialize` has not been called.
+    NotInitialized = 2,
+    /// Amount is negative.
+    InvalidAmount = 3,
+    /// The balance does not cover the amount.
+    InsufficientBalance = 4,
+    /// The spender's allowance does not cover the amount.
+    InsufficientAllowance = 5,
+    /// A non-zero allowance would expire before the current ledger.
+    InvalidExpirationLedger = 6,
+    /// A balance or the total supply would overflow i128.
+    Overflow = 7,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    /// Address of the `RevoraRevenueShare` contract notified of balance changes.
+    RevenueContract,
+    Metadata,
+    TotalSupply,
+    Balance(Address),
+    /// Allowance for (from, spender).
+    Allowance(Address, Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub decimal: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// The hook `RevoraRevenueShare` exposes to its offering tokens.
+#[contractclient(name = "RevenueShareHookClient")]
+pub trait RevenueShareHook {
+    /// `from = None` for a mint, `to = None` for a burn.
+    fn on_token_transfer(
+        env: Env,
+        token: Address,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: i128,
+    );
+}
+
+#[contract]
+pub struct RevoraToken;
+
+#[contractimpl]
+impl RevoraToken {
+    /// Set the admin (minter), the revenue contract to notify, and token metadata.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        revenue_contract: Address,
+        decimal: u32,
+        name: String,
+        symbol: String,
+    ) -> Result<(), TokenError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(TokenError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::RevenueContract, &revenue_contract);
+        env.storage().instance().set(&DataKey::Metadata, &TokenMetadata { decimal, name, symbol });
+        Ok(())
+    }
+
+    /// Mint `amount` to `to`. Admin only.
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+        Self::require_non_negative(amount)?;
+
+        let supply =
+            Self::total_supply(env.clone()).checked_add(amount).ok_or(TokenError::Overflow)?;
+        Self::credit(&env, &to, amount);
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+        Self::notify(&env, None, Some(to.clone()), amount);
+
+        env.events().publish((symbol_short!("mint"), admin, to), amount);
+        Ok(())
+    }
+
+    /// Hand the admin role to `new_admin`. Admin only.
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), TokenError> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+
+        env.events().publish((symbol_short!("set_admin"), admin), new_admin);
+        Ok(())
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::TotalSupply).unwrap_or(0)
+    }
+
+    pub fn get_revenue_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RevenueContract)
+    }
+
+    fn get_admin(env: &Env) -> Result<Address, TokenError> {
+        env.storage().instance().get(&DataKey::Admin).ok_or(TokenError::NotInitialized)
+    }
+
+    fn require_non_negative(amount: i128) -> Result<(), TokenError> {
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        Ok(())
+    }
+
+    fn credit(env: &Env, holder: &Address, amount: i128) {
+        let key = DataKey::Balance(holder.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let balance = balance
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+        env.storage().persistent().set(&key, &balance);
+    }
+
+    fn debit(env: &Env, holder: &Address, amount: i128) {
+        let key = DataKey::Balance(holder.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if balance < amount {
+            panic_with_error!(env, TokenError::InsufficientBalance);
+        }
+        let balance = balance
+            .checked_sub(amount)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+        env.storage().persistent().set(&key, &balance);
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            panic_with_error!(env, TokenError::InsufficientAllowance);
+        }
+        if amount > 0 {
+            let key = DataKey::Allowance(from.clone(), spender.clone());
+            let mut value: AllowanceValue = env.storage().persistent().get(&key).unwrap();
+            value.amount -= amount;
+            env.storage().persistent().set(&key, &value);
+        }
+    }
+
+    fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+        if amount < 0 {
+            panic_with_error!(env, TokenError::InvalidAmount);
+        }
+        Self::debit(env, from, amount);
+        Self::credit(env, to, amount);
+        Self::notify(env, Some(from.clone()), Some(to.clone()), amount);
+
+        env.events().publish((symbol_short!("transfer"), from.clone(), to.clone()), amount);
+    }
+
+    fn burn_balance(env: &Env, from: &Address, amount: i128) {
+        if amount < 0 {
+            panic_with_error!(env, TokenError::InvalidAmount);
+        }
+        Self::debit(env, from, amount);
+        let supply = Self::total_supply(env.clone())
+            .checked_sub(amount)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+        Self::notify(env, Some(from.clone()), None, amount);
+
+        env.events().publish((symbol_short!("burn"), from.clone()), amount);
+    }
+
+    /// Report a balance change to the revenue contract; its errors abort the change.
+    fn notify(env: &Env, from: Option<Address>, to: Option<Address>, amount: i128) {
+        let revenue_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::RevenueContract)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::NotInitialized));
+        RevenueShareHookClient::new(env, &revenue_contract).on_token_transfer(
+            &env.current_contract_address(),
+            &from,
+            &to,
+            &amount,
+        );
+    }
+
+    fn metadata(env: &Env) -> TokenMetadata {
+        env.storage()
+            .instance()
+            .get(&DataKey::Metadata)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::NotInitialized))
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for RevoraToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let key = DataKey::Allowance(from, spender);
+        match env.storage().persistent().get::<_, AllowanceValue>(&key) {
+            Some(value) if value.expiration_ledger >= env.ledger().sequence() => value.amount,
+            _ => 0,
+        }
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        if amount < 0 {
+            panic_with_error!(&env, TokenError::InvalidAmount);
+        }
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, TokenError::InvalidExpirationLedger);
+        }
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().persistent().set(&key, &AllowanceValue { amount, expiration_ledger });
+
+        env.events()
+            .publish((symbol_short!("approve"), from, spender), (amount, expiration_ledger));
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Balance(id)).unwrap_or(0)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        Self::move_balance(&env, &from, &to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::move_balance(&env, &from, &to, amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        Self::burn_balance(&env, &from, amount);
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::burn_balance(&env, &from, amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        Self::metadata(&env).decimal
+    }
+
+    fn name(env: Env) -> String {
+        Self::metadata(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        Self::metadata(&env).symbol
+    }
+}
+
+mod test;
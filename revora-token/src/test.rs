@@ -0,0 +1,248 @@
+#![cfg(test)]
+
+use revora_contracts::{RevoraError, RevoraRevenueShare, RevoraRevenueShareClient};
+use soroban_sdk::{testutils::Address as _, token, Address, Env, String};
+
+use crate::{RevoraToken, RevoraTokenClient, TokenError};
+
+/// Revenue contract, hooked offering token and a funded payment token.
+/// Returns (env, revora, offering token, issuer, payment token).
+fn setup() -> (Env, RevoraRevenueShareClient<'static>, RevoraTokenClient<'static>, Address, Address)
+{
+    setup_with_share(10_000)
+}
+
+fn setup_with_share(
+    revenue_share_bps: u32,
+) -> (Env, RevoraRevenueShareClient<'static>, RevoraTokenClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let revora_id = env.register_contract(None, RevoraRevenueShare);
+    let revora = RevoraRevenueShareClient::new(&env, &revora_id);
+    let token = RevoraTokenClient::new(&env, &env.register_contract(None, RevoraToken));
+    let admin = Address::generate(&env);
+    token.initialize(
+        &admin,
+        &revora_id,
+        &7,
+        &String::from_str(&env, "Revora Offering"),
+        &String::from_str(&env, "RVO"),
+    );
+
+    let issuer = Address::generate(&env);
+    let payment_token = env.register_stellar_asset_contract(Address::generate(&env));
+    token::StellarAssetClient::new(&env, &payment_token).mint(&issuer, &1_000_000);
+
+    revora.register_offering(&issuer, &token.address, &revenue_share_bps, &payment_token);
+    revora.enable_token_hooks(&issuer, &token.address);
+
+    (env, revora, token, issuer, payment_token)
+}
+
+#[test]
+fn metadata_and_double_initialize() {
+    let (env, revora, token, _issuer, _payment_token) = setup();
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(token.symbol(), String::from_str(&env, "RVO"));
+    assert_eq!(token.get_revenue_contract(), Some(revora.address.clone()));
+
+    let r = token.try_initialize(
+        &Address::generate(&env),
+        &revora.address,
+        &7,
+        &String::from_str(&env, "Other"),
+        &String::from_str(&env, "OTH"),
+    );
+    assert!(r.is_err());
+}
+
+#[test]
+fn mint_sets_holder_shares() {
+    let (env, revora, token, issuer, payment_token) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    token.mint(&a, &600);
+    token.mint(&b, &400);
+
+    assert_eq!(token.total_supply(), 1_000);
+    assert_eq!(revora.get_holder_share_units(&token.address, &a), 600);
+    assert_eq!(revora.get_holder_share(&token.address, &a), 6_000);
+    assert_eq!(revora.get_total_allocated_bps(&token.address), 10_000);
+    assert_eq!(revora.get_holder_count(&token.address), 2);
+
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &1_000, &1);
+    assert_eq!(revora.get_claimable(&token.address, &a), 600);
+    assert_eq!(revora.get_claimable(&token.address, &b), 400);
+}
+
+#[test]
+fn supply_shares_only_the_offering_revenue_share() {
+    let (env, revora, token, issuer, payment_token) = setup_with_share(4_000);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    token.mint(&a, &600);
+    token.mint(&b, &400);
+
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &1_000, &1);
+    assert_eq!(revora.get_claimable(&token.address, &a), 240);
+    assert_eq!(revora.claim(&a, &token.address, &0), 240);
+    assert_eq!(revora.claim(&b, &token.address, &0), 160);
+}
+
+#[test]
+fn mint_past_i128_supply_fails() {
+    let (env, revora, token, _issuer, _payment_token) = setup();
+    let a = Address::generate(&env);
+    token.mint(&a, &i128::MAX);
+
+    let r = token.try_mint(&Address::generate(&env), &1);
+    assert_eq!(r.err().unwrap(), Ok(TokenError::Overflow));
+    assert_eq!(token.total_supply(), i128::MAX);
+    assert_eq!(revora.get_holder_count(&token.address), 1);
+}
+
+#[test]
+fn transfer_settles_revenue_accrued_before_it() {
+    let (env, revora, token, issuer, payment_token) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    token.mint(&a, &600);
+    token.mint(&b, &400);
+
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &1_000, &1);
+    token.transfer(&a, &b, &200);
+    assert_eq!(revora.get_holder_share_units(&token.address, &a), 400);
+    assert_eq!(revora.get_holder_share_units(&token.address, &b), 600);
+
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &1_000, &2);
+    assert_eq!(revora.claim(&a, &token.address, &0), 600 + 400);
+    assert_eq!(revora.claim(&b, &token.address, &0), 400 + 600);
+    assert_eq!(token::Client::new(&env, &payment_token).balance(&a), 1_000);
+}
+
+#[test]
+fn transfer_from_and_burn_update_shares() {
+    let (env, revora, token, issuer, payment_token) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let spender = Address::generate(&env);
+    token.mint(&a, &500);
+    token.mint(&b, &500);
+
+    token.approve(&a, &spender, &300, &1_000);
+    token.transfer_from(&spender, &a, &b, &300);
+    assert_eq!(token.allowance(&a, &spender), 0);
+    token.burn(&b, &600);
+
+    assert_eq!(token.total_supply(), 400);
+    assert_eq!(revora.get_holder_share_units(&token.address, &b), 200);
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &1_000, &1);
+    assert_eq!(revora.get_claimable(&token.address, &a), 500);
+    assert_eq!(revora.get_claimable(&token.address, &b), 500);
+}
+
+#[test]
+fn holder_leaving_and_returning_is_registered_once() {
+    let (env, revora, token, _issuer, _payment_token) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    token.mint(&a, &100);
+    token.transfer(&a, &b, &100);
+    token.transfer(&b, &a, &100);
+
+    assert_eq!(revora.get_holder_count(&token.address), 2);
+    assert_eq!(revora.get_holder_share_units(&token.address, &a), 100);
+}
+
+#[test]
+fn revenue_before_first_mint_goes_to_first_holders() {
+    let (env, revora, token, issuer, payment_token) = setup();
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &1_000, &1);
+
+    let a = Address::generate(&env);
+    token.mint(&a, &10);
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &500, &2);
+    assert_eq!(revora.claim(&a, &token.address, &0), 1_500);
+}
+
+#[test]
+fn uneven_split_never_overpays() {
+    let (env, revora, token, issuer, payment_token) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    token.mint(&a, &1);
+    token.mint(&b, &2);
+
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &100, &1);
+    assert_eq!(revora.get_claimable(&token.address, &a), 33);
+    assert_eq!(revora.get_claimable(&token.address, &b), 66);
+
+    // The division remainder is carried into the next deposit
+    revora.deposit_revenue(&issuer, &token.address, &payment_token, &2, &2);
+    assert_eq!(revora.get_claimable(&token.address, &a), 34);
+    assert_eq!(revora.get_claimable(&token.address, &b), 68);
+}
+
+#[test]
+fn manual_shares_and_mode_changes_are_rejected() {
+    let (env, revora, token, issuer, _payment_token) = setup();
+    let holder = Address::generate(&env);
+
+    let r = revora.try_set_holder_share(&issuer, &token.address, &holder, &1_000);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+    let r = revora.try_set_accumulator_mode(&issuer, &token.address, &false);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+}
+
+#[test]
+fn mint_fails_when_offering_has_no_hooks() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let revora_id = env.register_contract(None, RevoraRevenueShare);
+    let revora = RevoraRevenueShareClient::new(&env, &revora_id);
+    let token = RevoraTokenClient::new(&env, &env.register_contract(None, RevoraToken));
+    token.initialize(
+        &Address::generate(&env),
+        &revora_id,
+        &7,
+        &String::from_str(&env, "Revora Offering"),
+        &String::from_str(&env, "RVO"),
+    );
+    revora.register_offering(
+        &Address::generate(&env),
+        &token.address,
+        &10_000,
+        &Address::generate(&env),
+    );
+
+    assert!(token.try_mint(&Address::generate(&env), &100).is_err());
+    assert_eq!(token.total_supply(), 0);
+}
+
+#[test]
+fn only_the_token_can_call_the_hook() {
+    let (env, revora, token, _issuer, _payment_token) = setup();
+    env.set_auths(&[]);
+
+    let holder = Address::generate(&env);
+    let r = revora.try_on_token_transfer(&token.address, &None, &Some(holder.clone()), &100);
+    assert!(r.is_err());
+    assert_eq!(revora.get_holder_share_units(&token.address, &holder), 0);
+}
+
+#[test]
+fn sanctioned_address_cannot_receive_tokens() {
+    let (env, revora, token, _issuer, _payment_token) = setup();
+    let admin = Address::generate(&env);
+    revora.set_admin(&admin);
+    let a = Address::generate(&env);
+    let sanctioned = Address::generate(&env);
+    token.mint(&a, &100);
+    revora.sanction_add(&admin, &sanctioned);
+
+    assert!(token.try_mint(&sanctioned, &100).is_err());
+    assert!(token.try_transfer(&a, &sanctioned, &50).is_err());
+    assert_eq!(token.balance(&a), 100);
+    assert_eq!(revora.get_holder_share_units(&token.address, &sanctioned), 0);
+}
@@ -2,8 +2,8 @@
 #![deny(unsafe_code)]
 #![deny(clippy::dbg_macro, clippy::todo, clippy::unimplemented)]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Map,
-    String, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec,
 };
 
 /// Centralized contract error codes. Auth failures are signaled by host panic (require_auth).
@@ -33,15 +33,6 @@ pub enum RevoraError {
     ContractFrozen = 10,
     /// Revenue for this period is not yet claimable (delay not elapsed).
     ClaimDelayNotElapsed = 11,
-
-    /// Snapshot distribution is not enabled for this offering.
-    SnapshotNotEnabled = 12,
-    /// Provided snapshot reference is outdated or duplicates a previous one.
-    OutdatedSnapshot = 13,
-
-    /// Payout asset does not match the configured payout asset for this offering.
-    PayoutAssetMismatch = 12,
-
     /// A transfer is already pending for this offering.
     IssuerTransferPending = 12,
     /// No transfer is pending for this offering.
@@ -52,38 +43,58 @@ pub enum RevoraError {
     PayoutAssetMismatch = 15,
     /// Metadata string exceeds maximum allowed length.
     MetadataTooLarge = 16,
-    /// Caller is not authorized to perform this action.
-    NotAuthorized = 17,
-    /// Contract is not initialized (admin not set).
-    NotInitialized = 18,
     /// Amount is invalid (e.g. negative for deposit, or out of allowed range) (#35).
     InvalidAmount = 17,
     /// period_id is invalid (e.g. zero when required to be positive) (#35).
     InvalidPeriodId = 18,
+    /// Merkle proof does not match the published root for this period.
+    InvalidMerkleProof = 19,
+    /// Merkle leaf for this (period, leaf index) has already been claimed.
+    MerkleLeafAlreadyClaimed = 20,
+    /// Holder shares for this offering would exceed its revenue_share_bps.
+    ShareAllocationExceeded = 21,
+    /// Operation conflicts with the offering's distribution mode (e.g. changing mode after
+    /// deposits, or a period-list-only feature on an accumulator-mode offering).
+    DistributionModeConflict = 22,
+    /// The period's claim deadline has passed; its unclaimed revenue can only be swept.
+    ClaimDeadlinePassed = 23,
+    /// The period has no claim deadline or it has not passed yet.
+    PeriodNotExpired = 24,
+    /// The period's unclaimed revenue was already swept.
+    PeriodAlreadySwept = 25,
+    /// The period's rounding remainder was already settled into the dust ledger.
+    DustAlreadySettled = 26,
+    /// A payout or share computation overflowed i128.
+    ArithmeticOverflow = 27,
+    /// The period was already claimed, precedes the holder's entitlement start, or is a
+    /// Merkle period.
+    PeriodNotClaimable = 28,
+    /// The offering has a whitelist and the holder is not on it.
+    NotWhitelisted = 29,
+    /// The holder is not blacklisted for this offering.
+    HolderNotBlacklisted = 30,
+    /// A blacklist entry's expiry is not in the future.
+    InvalidExpiry = 31,
+    /// The holder is on the platform-wide sanctions list.
+    HolderSanctioned = 32,
+    /// Snapshot distribution is not enabled for this offering.
+    SnapshotNotEnabled = 33,
+    /// Provided snapshot reference is outdated or duplicates a previous one.
+    OutdatedSnapshot = 34,
+    /// Caller is not authorized to perform this action.
+    NotAuthorized = 35,
+    /// Contract is not initialized (admin not set).
+    NotInitialized = 36,
+    /// Snapshot reference is ahead of the current ledger sequence.
+    SnapshotInFuture = 37,
+    /// No other holder has a share to redistribute a blacklisted holder's revenue to.
+    NoRemainingHolders = 38,
 }
 
 // ── Event symbols ────────────────────────────────────────────
 const EVENT_REVENUE_REPORTED: Symbol = symbol_short!("rev_rep");
-const EVENT_BL_ADD: Symbol          = symbol_short!("bl_add");
-const EVENT_BL_REM: Symbol          = symbol_short!("bl_rem");
-const EVENT_WL_ADD: Symbol          = symbol_short!("wl_add");
-const EVENT_WL_REM: Symbol          = symbol_short!("wl_rem");
-
-// ── Storage key ──────────────────────────────────────────────
-/// One blacklist map per offering, keyed by the offering's token address.
-///
-/// Blacklist precedence rule: a blacklisted address is **always** excluded
-/// from payouts, regardless of any whitelist or investor registration.
-/// If the same address appears in both a whitelist and this blacklist,
-/// the blacklist wins unconditionally.
-///
-/// Whitelist is optional per offering. When enabled (non-empty), only
-/// whitelisted addresses are eligible for revenue distribution.
-/// When disabled (empty), all non-blacklisted holders are eligible.
-#[contracttype]
-pub enum DataKey {
-    Blacklist(Address),
-    Whitelist(Address),
+const EVENT_WL_ADD: Symbol = symbol_short!("wl_add");
+const EVENT_WL_REM: Symbol = symbol_short!("wl_rem");
 const EVENT_REVENUE_REPORTED_ASSET: Symbol = symbol_short!("rev_repa");
 const EVENT_REVENUE_REPORT_INITIAL: Symbol = symbol_short!("rev_init");
 const EVENT_REVENUE_REPORT_INITIAL_ASSET: Symbol = symbol_short!("rev_inia");
@@ -93,6 +104,7 @@ const EVENT_REVENUE_REPORT_REJECTED: Symbol = symbol_short!("rev_rej");
 const EVENT_REVENUE_REPORT_REJECTED_ASSET: Symbol = symbol_short!("rev_reja");
 const EVENT_BL_ADD: Symbol = symbol_short!("bl_add");
 const EVENT_BL_REM: Symbol = symbol_short!("bl_rem");
+const EVENT_LIST_MIGRATED: Symbol = symbol_short!("lst_migr");
 // Versioned event symbols (v1). We emit legacy events for compatibility
 // and also emit explicit v1 events that include a leading `version` field.
 const EVENT_OFFER_REG_V1: Symbol = symbol_short!("ofr_reg1");
@@ -137,11 +149,6 @@ pub struct Proposal {
  
 const EVENT_SNAP_CONFIG: Symbol = symbol_short!("snap_cfg");
 
-const EVENT_INIT: Symbol = symbol_short!("init");
-const EVENT_PAUSED: Symbol = symbol_short!("paused");
-const EVENT_UNPAUSED: Symbol = symbol_short!("unpaused");
- 
-
 const EVENT_ISSUER_TRANSFER_PROPOSED: Symbol = symbol_short!("iss_prop");
 const EVENT_ISSUER_TRANSFER_ACCEPTED: Symbol = symbol_short!("iss_acc");
 const EVENT_ISSUER_TRANSFER_CANCELLED: Symbol = symbol_short!("iss_canc");
@@ -157,8 +164,38 @@ const EVENT_METADATA_UPDATED: Symbol = symbol_short!("meta_upd");
 const EVENT_MIN_REV_THRESHOLD_SET: Symbol = symbol_short!("min_rev");
 /// Emitted when reported revenue is below the offering's minimum threshold; no distribution triggered (#25).
 const EVENT_REV_BELOW_THRESHOLD: Symbol = symbol_short!("rev_below");
+const EVENT_MERKLE_ROOT: Symbol = symbol_short!("mrkl_root");
+const EVENT_MERKLE_CLAIM: Symbol = symbol_short!("mrkl_clm");
+const EVENT_BACK_PERIODS: Symbol = symbol_short!("back_prd");
+const EVENT_ACC_MODE: Symbol = symbol_short!("acc_mode");
+const EVENT_CLAIM_ACC: Symbol = symbol_short!("claim_acc");
+const EVENT_KEEPER_SET: Symbol = symbol_short!("keeper");
+const EVENT_PUSH: Symbol = symbol_short!("push");
+const EVENT_HOLDERS_REG: Symbol = symbol_short!("hldr_reg");
+const EVENT_CLAIM_DEADLINE_SET: Symbol = symbol_short!("clm_dline");
+const EVENT_TREASURY_SET: Symbol = symbol_short!("treasury");
+const EVENT_SWEEP: Symbol = symbol_short!("sweep");
+const EVENT_FEE_COLLECTED: Symbol = symbol_short!("fee_coll");
+const EVENT_FEE_WITHDRAWN: Symbol = symbol_short!("fee_wdraw");
+const EVENT_DUST_SETTLED: Symbol = symbol_short!("dust");
+const EVENT_DUST_RECLAIMED: Symbol = symbol_short!("dust_recl");
+const EVENT_DUST_ROLLED: Symbol = symbol_short!("dust_roll");
+const EVENT_SHARE_PRECISION: Symbol = symbol_short!("shr_prec");
+const EVENT_TOKEN_HOOKS: Symbol = symbol_short!("tok_hooks");
+const EVENT_SHARE_SYNC: Symbol = symbol_short!("shr_sync");
+const EVENT_TWAB_CONFIG: Symbol = symbol_short!("twab_cfg");
+const EVENT_RELEASE_SET: Symbol = symbol_short!("release");
+const EVENT_BL_POLICY: Symbol = symbol_short!("bl_policy");
+const EVENT_BL_SETTLED: Symbol = symbol_short!("bl_settle");
+const EVENT_ESCROW_RELEASED: Symbol = symbol_short!("esc_rel");
+const EVENT_BL_REDIST: Symbol = symbol_short!("bl_redist");
+const EVENT_COMPLIANCE_SET: Symbol = symbol_short!("cmpl_set");
+const EVENT_SANCTION_ADD: Symbol = symbol_short!("snc_add");
+const EVENT_SANCTION_REM: Symbol = symbol_short!("snc_rem");
 
 const BPS_DENOMINATOR: i128 = 10_000;
+/// Fixed-point scale of the revenue-per-bps accumulator.
+const ACC_SCALE: i128 = 1_000_000_000_000;
 
 /// Represents a revenue-share offering registered on-chain.
 /// Offerings are immutable once registered.
@@ -222,6 +259,174 @@ pub struct SimulateDistributionResult {
     pub payouts: Vec<(Address, i128)>,
 }
 
+/// How a period's revenue becomes claimable after its deposit: nothing until
+/// `cliff_secs` have passed, then linearly over `linear_secs` (all at once if 0).
+/// The default (both 0) releases everything at deposit.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReleaseSchedule {
+    pub cliff_secs: u64,
+    pub linear_secs: u64,
+}
+
+/// Why, by whom and until when an investor is blacklisted for an offering.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlacklistRecord {
+    pub investor: Address,
+    /// Issuer-defined reason code (0 = unspecified).
+    pub reason: u32,
+    /// Hash of off-chain evidence for the entry; all zero if none was given.
+    pub evidence_hash: BytesN<32>,
+    /// The issuer or admin that added the entry; the contract's own address for
+    /// entries listed before records were kept.
+    pub authority: Address,
+    pub added_at: u64,
+    /// Time from which the entry no longer applies; `None` keeps it until removed.
+    pub expires_at: Option<u64>,
+}
+
+/// Size and content hash of an offering's blacklist or whitelist, which events carry
+/// instead of the list. `hash` is the XOR of `sha256(xdr(address))` over the listed
+/// addresses (all zero when empty), so it does not depend on their order.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListDigest {
+    pub count: u32,
+    pub hash: BytesN<32>,
+}
+
+/// What happens to a blacklisted holder's revenue when `settle_blacklisted_revenue`
+/// takes it out of their pending periods.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlacklistPolicy {
+    /// Held for the holder and paid to them once they are eligible again.
+    #[default]
+    Escrow = 0,
+    /// Shared at once among the offering's other holders, pro rata to their shares.
+    Redistribute = 1,
+    /// Transferred to the issuer.
+    ReturnToIssuer = 2,
+}
+
+/// An unclaimed period and the earliest time `claim` can pay from it.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingPeriod {
+    pub period_id: u64,
+    pub claimable_at: u64,
+}
+
+/// A holder's share from `timestamp` on, with the share-seconds accrued before it.
+/// Recorded on every share change; backs TWAB entitlement (see `set_twab_config`).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TwabObservation {
+    /// Ledger timestamp of the share change.
+    pub timestamp: u64,
+    /// Holder share in the offering's share units from `timestamp` on.
+    pub share: i128,
+    /// Sum of share * seconds held up to `timestamp`.
+    pub cumulative: i128,
+}
+
+/// A holder's share as of a given period index (#share checkpoints).
+/// The share applies to every period deposited at or after `from_period_index`
+/// until the next checkpoint.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareCheckpoint {
+    /// First period index (position in the deposit order) this share applies to.
+    pub from_period_index: u32,
+    /// Holder share in basis points.
+    pub share_bps: u32,
+}
+
+/// A `ShareCheckpoint` in share units, recorded for offerings with a finer
+/// `SharePrecision` than bps.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareCheckpointUnits {
+    /// First period index (position in the deposit order) this share applies to.
+    pub from_period_index: u32,
+    /// Holder share in the offering's share units.
+    pub share: i128,
+}
+
+/// Per-offering deposit/claim totals. `outstanding` is what the contract still owes
+/// the offering's holders:
+/// `total_deposited - total_claimed - total_swept - total_dust_reclaimed - dust`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingLiabilities {
+    pub total_deposited: i128,
+    pub total_claimed: i128,
+    /// Unclaimed revenue of expired periods swept to the issuer or treasury, and
+    /// blacklisted holders' revenue returned to the issuer.
+    pub total_swept: i128,
+    /// Rounding remainders returned to the issuer by `reclaim_dust`.
+    pub total_dust_reclaimed: i128,
+    /// Rounding remainders held by the contract (pending plus rolled forward).
+    pub dust: i128,
+    pub outstanding: i128,
+}
+
+/// Rounding remainders of an offering's settled periods.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DustLedger {
+    /// Settled dust the issuer can still reclaim or roll forward.
+    pub pending: i128,
+    /// Dust that will be added to the offering's next deposit.
+    pub rolled: i128,
+    /// Cumulative dust returned to the issuer.
+    pub reclaimed: i128,
+}
+
+/// Per-offering outcome of `claim_many`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimResult {
+    pub token: Address,
+    /// Amount paid for this offering (0 on failure).
+    pub amount: i128,
+    /// `RevoraError` code if this entry failed; `None` on success.
+    pub error: Option<u32>,
+}
+
+/// Outcome of one `push_distribution` page.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PushDistributionResult {
+    /// Holders paid in this page and the amount each received.
+    pub paid: Vec<(Address, i128)>,
+    /// Blacklisted holders in this page; nothing was paid to them.
+    pub skipped_blacklisted: Vec<Address>,
+    /// Holders in this page missing from the offering's whitelist; nothing was paid to them.
+    pub skipped_not_whitelisted: Vec<Address>,
+    /// Holders in this page on the platform-wide sanctions list; nothing was paid to them.
+    pub skipped_sanctioned: Vec<Address>,
+    /// Holders in this page whose claim failed for another reason, with the
+    /// `RevoraError` code (e.g. `ArithmeticOverflow`); nothing was paid to them.
+    pub failed: Vec<(Address, u32)>,
+    /// Cursor for the next page, or `None` when the registry is exhausted.
+    pub next_cursor: Option<u32>,
+}
+
+/// Solvency check for one payment token across all offerings.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolvencyReport {
+    /// Outstanding liabilities of every offering paid in this token, plus
+    /// platform fees not yet withdrawn and rounding dust held (see `get_total_dust`).
+    pub liabilities: i128,
+    /// The contract's actual balance of the payment token.
+    pub balance: i128,
+    /// `liabilities - balance` when positive, otherwise 0.
+    pub shortfall: i128,
+}
+
 /// Defines how fractional shares are handled during distribution calculations.
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -230,18 +435,46 @@ pub enum RoundingMode {
     Truncation = 0,
     /// Standard rounding: share = round((amount * bps) / 10000), where >= 0.5 rounds up.
     RoundHalfUp = 1,
+    /// Banker's rounding: like `RoundHalfUp`, but exact halves round to the even neighbour.
+    RoundHalfEven = 2,
+    /// Round any fraction up (toward positive infinity).
+    Ceiling = 3,
+    /// Hare-Niemeyer allocation: every holder gets the truncated share, then the remaining
+    /// units go one each to the largest remainders (see `allocate_batch`). Only applies
+    /// where all shares are known at once (`allocate_batch`, `simulate_distribution`);
+    /// a single share, as in a holder's `claim`, truncates and the remainder is left as
+    /// period dust.
+    LargestRemainder = 4,
 }
 
-/// Storage keys: offerings use OfferCount/OfferItem; blacklist uses Blacklist(token).
+/// Unit in which an offering's holder shares are stored and computed.
+/// The bps entrypoints (`set_holder_share`, `get_holder_share`, ...) convert to and from it.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SharePrecision {
+    /// Basis points: 10_000 units = 100% (default).
+    Bps = 0,
+    /// Parts per million: 1_000_000 units = 100%.
+    Ppm = 1,
+    /// 18-decimal fixed point: 10^18 units = 100%.
+    Fixed18 = 2,
+}
+
+/// Storage keys: offerings use OfferCount/OfferItem; blacklist and whitelist entries are
+/// kept per address under `DistributionKey`, and the whole-list `Blacklist`,
+/// `BlacklistOrder` and `Whitelist` keys are only read until `migrate_legacy_lists` empties them.
 /// Multi-period claim keys use PeriodRevenue/PeriodEntry/PeriodCount for per-offering
 /// period tracking, HolderShare for holder allocations, LastClaimedIdx for claim progress,
 /// and PaymentToken for the token used to pay out revenue.
 /// `RevenueIndex` and `RevenueReports` track reported (un-deposited) revenue totals and details.
 #[contracttype]
 pub enum DataKey {
+    /// Legacy per-token blacklist map.
     Blacklist(Address),
-    /// Per-token: blacklist addresses in insertion order for deterministic get_blacklist (#38).
+    /// Legacy per-token: blacklist addresses in insertion order (#38).
     BlacklistOrder(Address),
+    /// Legacy per-token whitelist map.
+    Whitelist(Address),
     OfferCount(Address),
     OfferItem(Address, u32),
     /// Per (issuer, token): concentration limit config.
@@ -262,7 +495,7 @@ pub enum DataKey {
     PeriodEntry(Address, u32),
     /// Total number of deposited periods for an offering token.
     PeriodCount(Address),
-    /// Holder's share in basis points for (offering_token, holder).
+    /// Holder's share in the offering's share units for (offering_token, holder).
     HolderShare(Address, Address),
     /// Next period index to claim for (offering_token, holder).
     LastClaimedIdx(Address, Address),
@@ -297,7 +530,8 @@ pub enum DataKey {
     PendingIssuerTransfer(Address),
     /// Current issuer lookup by offering token: token -> issuer.
     OfferingIssuer(Address),
-    /// Testnet mode flag; when true, enables fee-free/simplified behavior (#24).
+    /// Testnet mode flag; when true, relaxes certain validations (#24). Platform fees
+    /// are still charged.
     TestnetMode,
 
     /// Safety role address for emergency pause (#7).
@@ -326,6 +560,174 @@ pub enum DataKey {
     IssuerRegistered(Address),
     /// Total deposited revenue for an offering token (#39).
     DepositedRevenue(Address),
+    /// Merkle root of (leaf_index, holder, amount) leaves for (offering_token, period_id).
+    MerkleRoot(Address, u64),
+    /// Set once the leaf at (offering_token, period_id, leaf_index) has been claimed.
+    MerkleClaimed(Address, u64, u32),
+    /// Total paid out via Merkle proofs for (offering_token, period_id).
+    MerkleClaimedTotal(Address, u64),
+    /// Number of share checkpoints recorded for (offering_token, holder).
+    ShareCheckpointCount(Address, Address),
+    /// Share checkpoint at (offering_token, holder, sequential_index).
+    ShareCheckpoint(Address, Address, u32),
+    /// First period index (offering_token, holder) is entitled to claim.
+    EntitlementStart(Address, Address),
+    /// Sum of all holder shares (bps) for an offering token.
+    TotalAllocatedBps(Address),
+}
+
+/// Storage keys for the per-offering holder registry and push distribution.
+/// Variant names must stay distinct from `DataKey`'s (see `AccountingKey`).
+#[contracttype]
+pub enum DistributionKey {
+    /// Number of holders registered for an offering token.
+    HolderCount(Address),
+    /// Holder at (offering_token, registration_index).
+    HolderAt(Address, u32),
+    /// Set once (offering_token, holder) is in the holder registry.
+    HolderRegistered(Address, Address),
+    /// Address allowed to push payouts for an offering token besides its issuer.
+    Keeper(Address),
+    /// Seconds after deposit until a period's unclaimed revenue expires (0 = never).
+    ClaimDeadlineSecs(Address),
+    /// Recipient of swept revenue for an offering token (defaults to the issuer).
+    Treasury(Address),
+    /// Set once (offering_token, period_id) has been swept.
+    PeriodSwept(Address, u64),
+    /// Time from which (offering_token, period_id) can no longer be claimed, fixed at
+    /// deposit from the offering's claim deadline and set to 0 when the period is swept.
+    /// Absent while neither applies.
+    PeriodDeadline(Address, u64),
+    /// Set once any period of an offering token has been deposited with a deadline.
+    HasPeriodDeadlines(Address),
+    /// Claim delay in seconds fixed for (offering_token, period_id) at its deposit;
+    /// absent if the period was deposited without one.
+    PeriodClaimDelay(Address, u64),
+    /// Set once any period of an offering token has been deposited with a claim delay.
+    HasClaimDelays(Address),
+    /// Set when (offering_token, period_id) pays out truncated because the offering's
+    /// rounding mode could have paid out more than the period's deposit.
+    PeriodTruncated(Address, u64),
+    /// Share precision of an offering token (defaults to bps).
+    SharePrecision(Address),
+    /// Share of (offering_token, holder) in share units, for offerings whose shares are
+    /// not plain bps (a finer precision, or token balances under token hooks).
+    HolderShareUnits(Address, Address),
+    /// Share checkpoint in share units at (offering_token, holder, sequential_index).
+    ShareCheckpointUnits(Address, Address, u32),
+    /// Sum of all holder shares in share units, alongside `HolderShareUnits`.
+    TotalAllocatedUnits(Address),
+    /// Snapshot reference that (offering_token, period_id) was deposited against.
+    PeriodSnapshot(Address, u64),
+    /// Per offering token: true once a period has been deposited against a snapshot.
+    SnapshotBound(Address),
+    /// Per offering token: true when holder shares mirror token balances via transfer hooks.
+    TokenHooks(Address),
+    /// Per offering token: whether new deposits use time-weighted entitlement.
+    TwabConfig(Address),
+    /// Per offering token: true once a period has been deposited with a TWAB window.
+    TwabBound(Address),
+    /// Start of the next TWAB window of an offering token (its last TWAB deposit).
+    TwabWindowStart(Address),
+    /// TWAB window (start, end) timestamps of (offering_token, period_id).
+    PeriodTwabWindow(Address, u64),
+    /// Number of TWAB observations for (offering_token, holder).
+    TwabObsCount(Address, Address),
+    /// TWAB observation at (offering_token, holder, index).
+    TwabObs(Address, Address, u32),
+    /// Sum of holder shares in share units when (offering_token, period_id) was deposited.
+    PeriodAllocated(Address, u64),
+    /// Blacklisted holders' revenue redistributed into (offering_token, period_id), as
+    /// (amount, summed share of the holders not yet paid from the period) pairs.
+    Redistributed(Address, u64),
+    /// Per offering token: true once revenue has been redistributed into a period.
+    HasRedistributions(Address),
+    /// Release schedule of an offering token's periods.
+    ReleaseSchedule(Address),
+    /// Release schedule (offering_token, period_id) was deposited under; absent if the
+    /// period was released at deposit.
+    PeriodRelease(Address, u64),
+    /// Amount (offering_token, holder) has claimed from a period that is still releasing.
+    PartialClaim(Address, Address, u64),
+    /// Periods (offering_token, holder) claimed ahead of their claim index, as a bitmap
+    /// over period indices with 128 periods per word.
+    ClaimedPeriods(Address, Address, u32),
+    /// What happens to the revenue of an offering token's blacklisted holders.
+    BlacklistPolicy(Address),
+    /// Blacklist record of (offering_token, investor); present while the investor is listed.
+    BlacklistRecord(Address, Address),
+    /// Entry count and hash of an offering token's blacklist.
+    BlacklistDigest(Address),
+    /// Blacklisted investor at (offering_token, index).
+    BlacklistAt(Address, u32),
+    /// Index of (offering_token, investor) in the blacklist.
+    BlacklistSlot(Address, Address),
+    /// Next free blacklist index of an offering token; removed entries leave a gap.
+    BlacklistEnd(Address),
+    /// Entry count and hash of an offering token's whitelist.
+    WhitelistDigest(Address),
+    /// Whitelisted investor at (offering_token, index).
+    WhitelistAt(Address, u32),
+    /// Index of (offering_token, investor) in the whitelist; present while the investor is listed.
+    WhitelistSlot(Address, Address),
+    /// Next free whitelist index of an offering token; removed entries leave a gap.
+    WhitelistEnd(Address),
+    /// Address allowed to manage the sanctions list besides the admin.
+    Compliance,
+    /// Entry count and hash of the platform-wide sanctions list.
+    SanctionsDigest,
+    /// Sanctioned address at index.
+    SanctionsAt(u32),
+    /// Index of a sanctioned address in the sanctions list.
+    SanctionsSlot(Address),
+    /// Next free sanctions list index; removed entries leave a gap.
+    SanctionsEnd,
+}
+
+/// Storage keys for deposit/payout accounting. Kept apart from `DataKey`, which is
+/// close to the 50-case limit for contract types; variant names must stay distinct
+/// from `DataKey`'s since both serialize as `(Symbol, ..)`.
+#[contracttype]
+pub enum AccountingKey {
+    /// Cumulative amount deposited for an offering token.
+    TotalDeposited(Address),
+    /// Cumulative amount paid out to holders of an offering token.
+    TotalClaimed(Address),
+    /// Outstanding liabilities of all offerings paid in a payment token.
+    AssetLiability(Address),
+    /// Cumulative amount swept from expired periods of an offering token.
+    TotalSwept(Address),
+    /// Amount paid out by `claim` for (offering_token, period_id).
+    PeriodClaimed(Address, u64),
+    /// Platform fees collected in a payment token and not yet withdrawn.
+    PlatformFees(Address),
+    /// Per offering token: true when claims use the revenue-per-bps accumulator.
+    AccumulatorMode(Address),
+    /// Cumulative revenue per bps of share, scaled by `ACC_SCALE`.
+    RevenuePerBps(Address),
+    /// Accumulator value at the holder's last settlement for (offering_token, holder).
+    HolderAccCheckpoint(Address, Address),
+    /// Revenue settled to (offering_token, holder) but not yet claimed.
+    HolderAccrued(Address, Address),
+    /// Rounding remainder settled for (offering_token, period_id).
+    PeriodDust(Address, u64),
+    /// Sum of the shares, and of the payouts, of holders fully paid from
+    /// (offering_token, period_id).
+    PeriodPaidShare(Address, u64),
+    /// Settled dust of an offering token not yet reclaimed or rolled forward.
+    DustPending(Address),
+    /// Dust of an offering token to be added to its next deposit.
+    DustRolled(Address),
+    /// Cumulative dust reclaimed by the issuer of an offering token.
+    DustReclaimed(Address),
+    /// Dust held in a payment token across all offerings (pending plus rolled).
+    AssetDust(Address),
+    /// Scaled revenue of a token-hooked offering not yet spread over its supply.
+    AccCarry(Address),
+    /// Revenue of (offering_token, holder) escrowed while they were blacklisted or sanctioned.
+    Escrowed(Address, Address),
+    /// Offering tokens in which a holder has revenue escrowed.
+    EscrowedTokens(Address),
 }
 
 /// Maximum number of offerings returned in a single page.
@@ -338,6 +740,85 @@ const MAX_PLATFORM_FEE_BPS: u32 = 5_000;
 /// Keeps compute costs predictable within Soroban limits.
 const MAX_CLAIM_PERIODS: u32 = 50;
 
+/// Maximum number of offerings settled by one `claim_many` call. Each entry may
+/// itself walk up to `MAX_CLAIM_PERIODS` periods.
+const MAX_CLAIM_MANY_ENTRIES: u32 = 10;
+
+/// Interface an offering token must implement for snapshot-bound periods. `ledger` is
+/// the `snapshot_reference` passed to `deposit_revenue_with_snapshot`.
+#[contractclient(name = "SnapshotTokenClient")]
+pub trait SnapshotToken {
+    /// Balance of `holder` as of `ledger`.
+    fn balance_at(env: Env, holder: Address, ledger: u64) -> i128;
+    /// Total supply as of `ledger`.
+    fn total_supply_at(env: Env, ledger: u64) -> i128;
+}
+
+/// An offering's blacklist or whitelist, stored one key per address with an
+/// insertion-ordered index for paging. A removal leaves a gap at the entry's index, so
+/// later entries keep theirs. The platform-wide sanctions list is stored the same way
+/// under its own keys (see `sanctions_insert`).
+#[derive(Clone, Copy)]
+enum AddressList {
+    Blacklist,
+    Whitelist,
+}
+
+impl AddressList {
+    fn digest_key(self, token: &Address) -> DistributionKey {
+        match self {
+            AddressList::Blacklist => DistributionKey::BlacklistDigest(token.clone()),
+            AddressList::Whitelist => DistributionKey::WhitelistDigest(token.clone()),
+        }
+    }
+
+    fn end_key(self, token: &Address) -> DistributionKey {
+        match self {
+            AddressList::Blacklist => DistributionKey::BlacklistEnd(token.clone()),
+            AddressList::Whitelist => DistributionKey::WhitelistEnd(token.clone()),
+        }
+    }
+
+    /// Key of the list's whole-list `Map<Address, bool>` from before per-address storage.
+    fn legacy_key(self, token: &Address) -> DataKey {
+        match self {
+            AddressList::Blacklist => DataKey::Blacklist(token.clone()),
+            AddressList::Whitelist => DataKey::Whitelist(token.clone()),
+        }
+    }
+
+    fn at_key(self, token: &Address, index: u32) -> DistributionKey {
+        match self {
+            AddressList::Blacklist => DistributionKey::BlacklistAt(token.clone(), index),
+            AddressList::Whitelist => DistributionKey::WhitelistAt(token.clone(), index),
+        }
+    }
+
+    fn slot_key(self, token: &Address, addr: &Address) -> DistributionKey {
+        match self {
+            AddressList::Blacklist => DistributionKey::BlacklistSlot(token.clone(), addr.clone()),
+            AddressList::Whitelist => DistributionKey::WhitelistSlot(token.clone(), addr.clone()),
+        }
+    }
+}
+
+/// Per-claim values shared by the per-period payout helpers.
+struct PayoutContext {
+    /// `has_custom_periods` for the offering, so per-period lookups can be skipped.
+    custom_periods: bool,
+    mode: RoundingMode,
+    /// True once the offering has had a release schedule, so its periods may carry one.
+    releases: bool,
+    /// True once revenue has been redistributed into one of the offering's periods.
+    redistributions: bool,
+    /// True once a period of the offering has been deposited with a claim delay.
+    delays: bool,
+    now: u64,
+}
+
+/// Periods booked by a claim, each with what it paid.
+type BookedPeriods = Vec<(u64, i128)>;
+
 // ── Contract ─────────────────────────────────────────────────
 #[contract]
 pub struct RevoraRevenueShare;
@@ -371,9 +852,14 @@ impl RevoraRevenueShare {
         amount: i128,
         period_id: u64,
     ) -> Result<(), RevoraError> {
+        Self::require_positive_amount(amount)?;
+        Self::require_valid_period_id(period_id)?;
+
         // Verify offering exists
-        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
-            return Err(RevoraError::OfferingNotFound);
+        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+        if offering.payout_asset != payment_token {
+            return Err(RevoraError::PayoutAssetMismatch);
         }
 
         // Check period not already deposited
@@ -396,13 +882,85 @@ impl RevoraRevenueShare {
         let contract_addr = env.current_contract_address();
         token::Client::new(env, &payment_token).transfer(&issuer, &contract_addr, &amount);
 
+        // Platform fee is taken off the top; holders share the net amount (#6)
+        let fee = Self::calculate_platform_fee(env.clone(), amount);
+        let net_amount = amount - fee;
+        if fee > 0 {
+            Self::add_to_counter(env, &AccountingKey::PlatformFees(payment_token.clone()), fee);
+            env.events().publish(
+                (EVENT_FEE_COLLECTED, token.clone(), payment_token.clone()),
+                (period_id, amount, fee, net_amount),
+            );
+        }
+
+        // Track total deposited revenue per offering (#39)
+        let deposited_key = DataKey::DepositedRevenue(token.clone());
+        let total_deposited: i128 = env.storage().persistent().get(&deposited_key).unwrap_or(0);
+        env.storage().persistent().set(&deposited_key, &total_deposited.saturating_add(amount));
+
         // Store period revenue
-        env.storage().persistent().set(&rev_key, &amount);
+        Self::record_liability(env, &token, &payment_token, net_amount);
+        let mut revenue = net_amount;
+        if !Self::is_merkle_period(env, &token, period_id) {
+            revenue = revenue
+                .checked_add(Self::apply_rolled_dust(env, &token, &payment_token))
+                .ok_or(RevoraError::ArithmeticOverflow)?;
+        }
+        env.storage().persistent().set(&rev_key, &revenue);
+        if Self::is_merkle_period(env, &token, period_id) {
+            // Merkle periods are paid from their root only
+        } else if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            Self::accumulate_revenue(env, &token, revenue)?;
+        } else {
+            // What the period is split over if revenue is redistributed into it
+            env.storage().persistent().set(
+                &DistributionKey::PeriodAllocated(token.clone(), period_id),
+                &Self::total_allocated_units(env, &token),
+            );
+        }
+        Self::fix_period_rounding(env, &token, period_id, revenue)?;
 
         // Store deposit timestamp for time-delayed claims (#27)
         let deposit_time = env.ledger().timestamp();
         let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
         env.storage().persistent().set(&time_key, &deposit_time);
+        let delay_secs = Self::get_claim_delay(env.clone(), token.clone());
+        if delay_secs > 0 {
+            env.storage()
+                .persistent()
+                .set(&DistributionKey::PeriodClaimDelay(token.clone(), period_id), &delay_secs);
+            env.storage().persistent().set(&DistributionKey::HasClaimDelays(token.clone()), &true);
+        }
+        let deadline_secs = Self::get_claim_deadline(env.clone(), token.clone());
+        if deadline_secs > 0 {
+            env.storage().persistent().set(
+                &DistributionKey::PeriodDeadline(token.clone(), period_id),
+                &deposit_time.saturating_add(deadline_secs),
+            );
+            env.storage().persistent().set(&DistributionKey::HasPeriodDeadlines(token.clone()), &true);
+        }
+        let release = Self::get_release_schedule(env.clone(), token.clone());
+        if release != ReleaseSchedule::default() {
+            env.storage()
+                .persistent()
+                .set(&DistributionKey::PeriodRelease(token.clone(), period_id), &release);
+        }
+
+        // TWAB periods cover the time since the previous TWAB deposit; Merkle and
+        // snapshot-bound deposits leave the TWAB state alone
+        if Self::get_twab_config(env.clone(), token.clone())
+            && !Self::is_merkle_period(env, &token, period_id)
+            && Self::get_period_snapshot(env.clone(), token.clone(), period_id).is_none()
+        {
+            let start_key = DistributionKey::TwabWindowStart(token.clone());
+            let window_start: u64 = env.storage().persistent().get(&start_key).unwrap_or(0);
+            env.storage().persistent().set(
+                &DistributionKey::PeriodTwabWindow(token.clone(), period_id),
+                &(window_start, deposit_time),
+            );
+            env.storage().persistent().set(&start_key, &deposit_time);
+            env.storage().persistent().set(&DistributionKey::TwabBound(token.clone()), &true);
+        }
 
         // Append to indexed period list
         let count_key = DataKey::PeriodCount(token.clone());
@@ -413,15 +971,19 @@ impl RevoraRevenueShare {
 
         env.events().publish(
             (EVENT_REV_DEPOSIT, issuer, token),
-            (payment_token, amount, period_id),
+            (payment_token, amount, period_id, net_amount),
         );
         Ok(())
+    }
+
     /// Return true if the contract is in event-only mode.
     pub fn is_event_only(env: &Env) -> bool {
         env.storage()
             .persistent()
             .get::<DataKey, bool>(&DataKey::EventOnlyMode)
             .unwrap_or(false)
+    }
+
     /// Input validation (#35): require amount > 0 for transfers/deposits.
     fn require_positive_amount(amount: i128) -> Result<(), RevoraError> {
         if amount <= 0 {
@@ -446,18 +1008,6 @@ impl RevoraRevenueShare {
         Ok(())
     }
 
-
-    /// Initialize the contract with an admin and an optional safety role.
-    ///
-    /// This method follows the singleton pattern and can only be called once.
-    ///
-    /// ### Parameters
-    /// - `admin`: The primary administrative address with authority to pause/unpause and manage offerings.
-    /// - `safety`: Optional address allowed to trigger emergency pauses but not manage offerings.
-    ///
-    /// ### Panics
-    /// Panics if the contract has already been initialized.
-
     /// Get the current issuer for an offering token (used for auth checks after transfers).
     fn get_current_issuer(env: &Env, token: &Address) -> Option<Address> {
         let key = DataKey::OfferingIssuer(token.clone());
@@ -465,8 +1015,6 @@ impl RevoraRevenueShare {
     }
 
     /// Initialize admin and optional safety role for emergency pause (#7).
-    /// Can only be called once; panics if already initialized
-    pub fn initialize(env: Env, admin: Address, safety: Option<Address>) {
     /// `event_only` configures the contract to skip persistent business state (#72).
     /// Can only be called once; panics if already initialized.
     pub fn initialize(env: Env, admin: Address, safety: Option<Address>, event_only: Option<bool>) {
@@ -565,13 +1113,11 @@ impl RevoraRevenueShare {
         if env.storage().persistent().get::<DataKey, bool>(&DataKey::Paused).unwrap_or(false) {
             panic!("contract is paused");
         }
- 
     }
 
     // ── Offering management ───────────────────────────────────
 
     /// Register a new revenue-share offering.
-
     ///
     /// Once registered, an offering's parameters are immutable.
     ///
@@ -584,10 +1130,8 @@ impl RevoraRevenueShare {
     /// - `Ok(())` on success.
     /// - `Err(RevoraError::InvalidRevenueShareBps)` if `revenue_share_bps` exceeds 10000.
     /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
-
-    /// Returns `Err(RevoraError::InvalidRevenueShareBps)` if revenue_share_bps > 10000.
+    ///
     /// In testnet mode, bps validation is skipped to allow flexible testing.
-
     pub fn register_offering(
         env: Env,
         issuer: Address,
@@ -608,23 +1152,6 @@ impl RevoraRevenueShare {
         if !Self::is_event_only(&env) {
             let count_key = DataKey::OfferCount(issuer.clone());
             let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-        let count_key = DataKey::OfferCount(issuer.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-
-        let offering = Offering { issuer: issuer.clone(), token: token.clone(), revenue_share_bps };
-
-        let offering = Offering {
-            issuer: issuer.clone(),
-            token: token.clone(),
-            revenue_share_bps,
-            payout_asset: payout_asset.clone(),
-        };
-
-
-        let item_key = DataKey::OfferItem(issuer.clone(), count);
-        env.storage().persistent().set(&item_key, &offering);
-        env.storage().persistent().set(&count_key, &(count + 1));
             let offering = Offering {
                 issuer: issuer.clone(),
                 token: token.clone(),
@@ -661,8 +1188,6 @@ impl RevoraRevenueShare {
         }
 
 
-        env.events().publish((symbol_short!("offer_reg"), issuer), (token, revenue_share_bps));
-
         env.events().publish(
             (symbol_short!("offer_reg"), issuer.clone()),
             (token.clone(), revenue_share_bps, payout_asset.clone()),
@@ -734,15 +1259,9 @@ impl RevoraRevenueShare {
     /// - `Ok(())` on success.
     /// - `Err(RevoraError::ConcentrationLimitExceeded)` if enforcement is enabled and concentration exceeds limit.
     /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
-
-    /// Record a revenue report for an offering. Updates audit summary (#34).
-    /// Fails with `ConcentrationLimitExceeded` (#26) if concentration enforcement is on and current concentration exceeds limit.
-    /// In testnet mode, concentration enforcement is skipped.
-    /// `override_existing`: if true, allows overwriting a previously reported period.
-
     ///
-    /// The event payload includes the current blacklist so off-chain
-    /// distribution engines can filter recipients in the same atomic step.
+    /// The event payload includes a digest of the current blacklist so off-chain
+    /// distribution engines can check their copy in the same atomic step.
     pub fn report_revenue(
         env: Env,
         issuer: Address,
@@ -755,6 +1274,7 @@ impl RevoraRevenueShare {
         Self::require_not_frozen(&env)?;
         Self::require_not_paused(&env);
         issuer.require_auth();
+        Self::require_non_negative_amount(amount)?;
 
         let event_only = Self::is_event_only(&env);
 
@@ -773,15 +1293,24 @@ impl RevoraRevenueShare {
                 return Err(RevoraError::PayoutAssetMismatch);
             }
 
+            // Per-offering minimum revenue threshold (#25): skip distribution when below threshold
+            let min_threshold =
+                Self::get_min_revenue_threshold(env.clone(), issuer.clone(), token.clone());
+            if min_threshold > 0 && amount < min_threshold {
+                env.events().publish(
+                    (EVENT_REV_BELOW_THRESHOLD, issuer, token),
+                    (amount, period_id, min_threshold),
+                );
+                return Ok(());
+            }
+
             // Skip concentration enforcement in testnet mode
             let testnet_mode = Self::is_testnet_mode(env.clone());
             if !testnet_mode {
                 // Holder concentration guardrail (#26): reject if enforce and over limit
                 let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
-                if let Some(config) = env
-                    .storage()
-                    .persistent()
-                    .get::<DataKey, ConcentrationLimitConfig>(&limit_key)
+                if let Some(config) =
+                    env.storage().persistent().get::<DataKey, ConcentrationLimitConfig>(&limit_key)
                 {
                     if config.enforce && config.max_bps > 0 {
                         let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
@@ -794,32 +1323,34 @@ impl RevoraRevenueShare {
             }
         }
 
-        let blacklist = if event_only {
-            Vec::new(&env)
-        } else {
-            Self::get_blacklist(env.clone(), token.clone())
-        };
+        let blacklist = Self::list_digest(&env, AddressList::Blacklist, &token);
 
         if !event_only {
-            let key = DataKey::RevenueReports(issuer.clone(), token.clone());
-            let mut reports: Map<u64, (i128, u64)> = env
-                .storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or_else(|| Map::new(&env));
+            let report_key = DataKey::RevenueReports(issuer.clone(), token.clone());
+            let mut reports: Map<u64, (i128, u64)> =
+                env.storage().persistent().get(&report_key).unwrap_or_else(|| Map::new(&env));
             let current_timestamp = env.ledger().timestamp();
 
+            let idx_key = DataKey::RevenueIndex(token.clone(), period_id);
+            let mut cumulative_revenue: i128 =
+                env.storage().persistent().get(&idx_key).unwrap_or(0);
+
             match reports.get(period_id) {
                 Some((existing_amount, _timestamp)) => {
                     if override_existing {
+                        // Update index: remove old, add new
+                        cumulative_revenue = cumulative_revenue
+                            .checked_sub(existing_amount)
+                            .unwrap_or(cumulative_revenue)
+                            .checked_add(amount)
+                            .unwrap_or(amount);
+                        env.storage().persistent().set(&idx_key, &cumulative_revenue);
                         reports.set(period_id, (amount, current_timestamp));
-                        env.storage().persistent().set(&key, &reports);
-
+                        env.storage().persistent().set(&report_key, &reports);
                         env.events().publish(
                             (EVENT_REVENUE_REPORT_OVERRIDE, issuer.clone(), token.clone()),
                             (amount, period_id, existing_amount, blacklist.clone()),
                         );
-
                         env.events().publish(
                             (
                                 EVENT_REVENUE_REPORT_OVERRIDE_ASSET,
@@ -834,7 +1365,6 @@ impl RevoraRevenueShare {
                             (EVENT_REVENUE_REPORT_REJECTED, issuer.clone(), token.clone()),
                             (amount, period_id, existing_amount, blacklist.clone()),
                         );
-
                         env.events().publish(
                             (
                                 EVENT_REVENUE_REPORT_REJECTED_ASSET,
@@ -847,81 +1377,15 @@ impl RevoraRevenueShare {
                     }
                 }
                 None => {
-        Self::require_non_negative_amount(amount)?;
-
-        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
-            .ok_or(RevoraError::OfferingNotFound)?;
-        if offering.payout_asset != payout_asset {
-            return Err(RevoraError::PayoutAssetMismatch);
-        }
-
-        // Per-offering minimum revenue threshold (#25): skip distribution when below threshold
-        let min_threshold =
-            Self::get_min_revenue_threshold(env.clone(), issuer.clone(), token.clone());
-        if min_threshold > 0 && amount < min_threshold {
-            env.events().publish(
-                (EVENT_REV_BELOW_THRESHOLD, issuer, token),
-                (amount, period_id, min_threshold),
-            );
-            return Ok(());
-        }
-
-        // Skip concentration enforcement in testnet mode
-        let testnet_mode = Self::is_testnet_mode(env.clone());
-        if !testnet_mode {
-            // Holder concentration guardrail (#26): reject if enforce and over limit
-            let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
-            if let Some(config) =
-                env.storage().persistent().get::<DataKey, ConcentrationLimitConfig>(&limit_key)
-            {
-                if config.enforce && config.max_bps > 0 {
-                    let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
-                    let current_bps: u32 = env.storage().persistent().get(&curr_key).unwrap_or(0);
-                    if current_bps > config.max_bps {
-                        return Err(RevoraError::ConcentrationLimitExceeded);
-                    }
-                }
-            }
-        }
-
-        let blacklist = Self::get_blacklist(env.clone(), token.clone());
-        let current_timestamp = env.ledger().timestamp();
-
-        let key = DataKey::RevenueReports(issuer.clone(), token.clone());
-        let mut reports: Map<u64, (i128, u64)> =
-            env.storage().persistent().get(&key).unwrap_or_else(|| Map::new(&env));
-        let current_timestamp = env.ledger().timestamp();
-        let report_key = DataKey::RevenueReports(issuer.clone(), token.clone());
-        let mut reports: Map<u64, (i128, u64)> = env
-            .storage()
-            .persistent()
-            .get(&report_key)
-            .unwrap_or_else(|| Map::new(&env));
-
-        let idx_key = DataKey::RevenueIndex(token.clone(), period_id);
-        let mut cumulative_revenue: i128 = env.storage().persistent().get(&idx_key).unwrap_or(0);
-
-        match reports.get(period_id) {
-            Some((existing_amount, _timestamp)) => {
-                if override_existing {
-                    // Update index: remove old, add new
-                    cumulative_revenue = cumulative_revenue
-                        .checked_sub(existing_amount)
-                        .unwrap_or(cumulative_revenue)
-                        .checked_add(amount)
-                        .unwrap_or(amount);
-                    env.storage()
-                        .persistent()
-                        .set(&idx_key, &cumulative_revenue);
-
+                    // Initial report for this period
+                    cumulative_revenue = cumulative_revenue.checked_add(amount).unwrap_or(amount);
+                    env.storage().persistent().set(&idx_key, &cumulative_revenue);
                     reports.set(period_id, (amount, current_timestamp));
                     env.storage().persistent().set(&report_key, &reports);
-
                     env.events().publish(
                         (EVENT_REVENUE_REPORT_INITIAL, issuer.clone(), token.clone()),
                         (amount, period_id, blacklist.clone()),
                     );
-
                     env.events().publish(
                         (
                             EVENT_REVENUE_REPORT_INITIAL_ASSET,
@@ -939,85 +1403,33 @@ impl RevoraRevenueShare {
                 (EVENT_REVENUE_REPORT_INITIAL, issuer.clone(), token.clone()),
                 (amount, period_id, blacklist.clone()),
             );
-            None => {
-                // Initial report for this period
-                cumulative_revenue = cumulative_revenue.checked_add(amount).unwrap_or(amount);
-                env.storage()
-                    .persistent()
-                    .set(&idx_key, &cumulative_revenue);
-
-                reports.set(period_id, (amount, current_timestamp));
-                env.storage().persistent().set(&report_key, &reports);
-
-                env.events().publish(
-                    (EVENT_REVENUE_REPORT_INITIAL, issuer.clone(), token.clone()),
-                    (amount, period_id, blacklist.clone()),
-                );
-
-            env.events().publish(
-                (
-                    EVENT_REVENUE_REPORT_INITIAL_ASSET,
-                    issuer.clone(),
-                    token.clone(),
-                    payout_asset.clone(),
-                ),
-                (amount, period_id, blacklist.clone()),
-            );
         }
+
         env.events().publish(
             (EVENT_REVENUE_REPORTED, issuer.clone(), token.clone()),
             (amount, period_id, blacklist.clone()),
         );
-
         env.events().publish(
-            (
-                EVENT_REVENUE_REPORTED_ASSET,
-                issuer.clone(),
-                token.clone(),
-                payout_asset.clone(),
-            ),
+            (EVENT_REVENUE_REPORTED_ASSET, issuer.clone(), token.clone(), payout_asset.clone()),
             (amount, period_id),
         );
 
-        // Audit log summary (#34): maintain per-offering total revenue and report count
-        let summary_key = DataKey::AuditSummary(issuer.clone(), token.clone());
-        let mut summary: AuditSummary = env
-            .storage()
-            .persistent()
-            .get(&summary_key)
-            .unwrap_or(AuditSummary { total_revenue: 0, report_count: 0 });
-        summary.total_revenue = summary.total_revenue.saturating_add(amount);
-        summary.report_count = summary.report_count.saturating_add(1);
-        env.storage().persistent().set(&summary_key, &summary);
         // Optionally emit versioned v1 events for forward-compatible consumers
         if Self::is_event_versioning_enabled(env.clone()) {
             env.events().publish(
                 (EVENT_REV_INIT_V1, issuer.clone(), token.clone()),
                 (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
             );
-
             env.events().publish(
-                (
-                    EVENT_REV_INIA_V1,
-                    issuer.clone(),
-                    token.clone(),
-                    payout_asset.clone(),
-                ),
+                (EVENT_REV_INIA_V1, issuer.clone(), token.clone(), payout_asset.clone()),
                 (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
             );
-
             env.events().publish(
                 (EVENT_REV_REP_V1, issuer.clone(), token.clone()),
                 (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
             );
-
             env.events().publish(
-                (
-                    EVENT_REV_REPA_V1,
-                    issuer.clone(),
-                    token.clone(),
-                    payout_asset.clone(),
-                ),
+                (EVENT_REV_REPA_V1, issuer.clone(), token.clone(), payout_asset.clone()),
                 (EVENT_SCHEMA_VERSION, amount, period_id),
             );
         }
@@ -1025,14 +1437,11 @@ impl RevoraRevenueShare {
         if !event_only {
             // Audit log summary (#34): maintain per-offering total revenue and report count
             let summary_key = DataKey::AuditSummary(issuer.clone(), token.clone());
-            let mut summary: AuditSummary =
-                env.storage()
-                    .persistent()
-                    .get(&summary_key)
-                    .unwrap_or(AuditSummary {
-                        total_revenue: 0,
-                        report_count: 0,
-                    });
+            let mut summary: AuditSummary = env
+                .storage()
+                .persistent()
+                .get(&summary_key)
+                .unwrap_or(AuditSummary { total_revenue: 0, report_count: 0 });
             summary.total_revenue = summary.total_revenue.saturating_add(amount);
             summary.report_count = summary.report_count.saturating_add(1);
             env.storage().persistent().set(&summary_key, &summary);
@@ -1092,7 +1501,7 @@ impl RevoraRevenueShare {
     /// Add an investor to the per-offering blacklist.
     ///
     /// Blacklisted addresses are prohibited from claiming revenue for the specified token.
-    /// This operation is idempotent.
+    /// Same as `blacklist_add_with_reason` with reason 0, no evidence and no expiry.
     ///
     /// ### Parameters
     /// - `caller`: The address authorized to manage the blacklist. Must provide authentication.
@@ -1101,66 +1510,76 @@ impl RevoraRevenueShare {
     ///
     /// ### Returns
     /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the admin.
     /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
     pub fn blacklist_add(
         env: Env,
         caller: Address,
         token: Address,
         investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::blacklist_add_with_reason(env, caller, token, investor, 0, None, None)
+    }
+
+    /// Add an investor to the per-offering blacklist with a reason code, an optional
+    /// hash of off-chain evidence and an optional expiry time, from which the entry
+    /// no longer applies. Adding a listed investor replaces their record but keeps
+    /// their position in `get_blacklist`.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the admin.
+    /// - `Err(RevoraError::InvalidExpiry)` if `expires_at` is not in the future.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn blacklist_add_with_reason(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+        reason: u32,
+        evidence_hash: Option<BytesN<32>>,
+        expires_at: Option<u64>,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
         Self::require_not_paused(&env);
         caller.require_auth();
+        Self::require_issuer_or_admin(&env, &caller, &token)?;
+
+        let now = env.ledger().timestamp();
+        if expires_at.is_some_and(|expiry| expiry <= now) {
+            return Err(RevoraError::InvalidExpiry);
+        }
 
         if !Self::is_event_only(&env) {
-            let key = DataKey::Blacklist(token.clone());
-            let mut map: Map<Address, bool> = env
-                .storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or_else(|| Map::new(&env));
-
-            map.set(investor.clone(), true);
-            env.storage().persistent().set(&key, &map);
-        }
-        // Verify auth: caller must be issuer or admin
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-        let admin = Self::get_admin(env.clone()).ok_or(RevoraError::NotInitialized)?;
-
-        if caller != current_issuer && caller != admin {
-            return Err(RevoraError::NotAuthorized);
-        }
-
-        let key = DataKey::Blacklist(token.clone());
-        let mut map: Map<Address, bool> =
-            env.storage().persistent().get(&key).unwrap_or_else(|| Map::new(&env));
-
-        let was_present = map.get(investor.clone()).unwrap_or(false);
-        map.set(investor.clone(), true);
-        env.storage().persistent().set(&key, &map);
-
-        env.events().publish((EVENT_BL_ADD, token, caller), investor);
-        // Maintain insertion order for deterministic get_blacklist (#38)
-        if !was_present {
-            let order_key = DataKey::BlacklistOrder(token.clone());
-            let mut order: Vec<Address> = env
-                .storage()
-                .persistent()
-                .get(&order_key)
-                .unwrap_or_else(|| Vec::new(&env));
-            order.push_back(investor.clone());
-            env.storage().persistent().set(&order_key, &order);
+            Self::list_insert(&env, AddressList::Blacklist, &token, &investor);
+            let record = BlacklistRecord {
+                investor: investor.clone(),
+                reason,
+                evidence_hash: evidence_hash
+                    .clone()
+                    .unwrap_or_else(|| BytesN::from_array(&env, &[0; 32])),
+                authority: caller.clone(),
+                added_at: now,
+                expires_at,
+            };
+            let record_key = DistributionKey::BlacklistRecord(token.clone(), investor.clone());
+            env.storage().persistent().set(&record_key, &record);
         }
 
-        env.events()
-            .publish((EVENT_BL_ADD, token, caller), investor);
+        let digest = Self::list_digest(&env, AddressList::Blacklist, &token);
+        env.events().publish(
+            (EVENT_BL_ADD, token, caller),
+            (investor, reason, evidence_hash, expires_at, digest),
+        );
         Ok(())
     }
 
     /// Remove an investor from the per-offering blacklist.
     ///
-    /// Re-enables the address to claim revenue for the specified token.
+    /// Re-enables the address to claim revenue for the specified token and, if it
+    /// is eligible again, pays out its escrowed revenue (see `settle_blacklisted_revenue`).
     /// This operation is idempotent.
     ///
     /// ### Parameters
@@ -1170,6 +1589,8 @@ impl RevoraRevenueShare {
     ///
     /// ### Returns
     /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the admin.
     /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
     pub fn blacklist_remove(
         env: Env,
@@ -1180,258 +1601,681 @@ impl RevoraRevenueShare {
         Self::require_not_frozen(&env)?;
         Self::require_not_paused(&env);
         caller.require_auth();
+        Self::require_issuer_or_admin(&env, &caller, &token)?;
 
-        if !Self::is_event_only(&env) {
-            let key = DataKey::Blacklist(token.clone());
-            let mut map: Map<Address, bool> = env
-                .storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or_else(|| Map::new(&env));
-        // Verify auth: caller must be issuer or admin
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-        let admin = Self::get_admin(env.clone()).ok_or(RevoraError::NotInitialized)?;
+        let record_key = DistributionKey::BlacklistRecord(token.clone(), investor.clone());
+        let reason = env
+            .storage()
+            .persistent()
+            .get::<_, BlacklistRecord>(&record_key)
+            .map(|record| record.reason)
+            .unwrap_or(0);
 
-        if caller != current_issuer && caller != admin {
-            return Err(RevoraError::NotAuthorized);
+        if !Self::is_event_only(&env) {
+            Self::list_remove(&env, AddressList::Blacklist, &token, &investor);
+            env.storage().persistent().remove(&record_key);
+            Self::release_escrow(&env, &token, &investor);
         }
 
-        let key = DataKey::Blacklist(token.clone());
-        let mut map: Map<Address, bool> =
-            env.storage().persistent().get(&key).unwrap_or_else(|| Map::new(&env));
-
-            map.remove(investor.clone());
-            env.storage().persistent().set(&key, &map);
-        }
+        let digest = Self::list_digest(&env, AddressList::Blacklist, &token);
+        env.events().publish((EVENT_BL_REM, token, caller), (investor, reason, digest));
+        Ok(())
+    }
 
-        // Rebuild order vec so get_blacklist stays deterministic (#38)
-        let order_key = DataKey::BlacklistOrder(token.clone());
-        let old_order: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&order_key)
-            .unwrap_or_else(|| Vec::new(&env));
-        let mut new_order = Vec::new(&env);
-        for i in 0..old_order.len() {
-            let addr = old_order.get(i).unwrap();
-            if map.get(addr.clone()).unwrap_or(false) {
-                new_order.push_back(addr);
+    /// Returns `true` if `investor` is blacklisted for `token`'s offering and the
+    /// entry has not expired.
+    pub fn is_blacklisted(env: Env, token: Address, investor: Address) -> bool {
+        match Self::blacklist_record(&env, &token, &investor) {
+            Some(BlacklistRecord { expires_at: Some(expiry), .. }) => {
+                env.ledger().timestamp() < expiry
             }
+            Some(_) => true,
+            None => false,
         }
-        env.storage().persistent().set(&order_key, &new_order);
+    }
 
-        env.events().publish((EVENT_BL_REM, token, caller), investor);
-        Ok(())
+    /// Return a page of `token`'s blacklist records, expired ones included, and the
+    /// next cursor. Limit capped at MAX_PAGE_LIMIT (20); see `get_blacklist_page` for
+    /// ordering and cursors.
+    pub fn get_blacklist(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<BlacklistRecord>, Option<u32>) {
+        let (investors, next_cursor) =
+            Self::list_page(&env, AddressList::Blacklist, &token, start, limit);
+        let mut results = Vec::new(&env);
+        for investor in investors.iter() {
+            results.push_back(Self::blacklist_record(&env, &token, &investor).unwrap());
+        }
+        (results, next_cursor)
     }
 
-    /// Returns `true` if `investor` is blacklisted for `token`'s offering.
-    pub fn is_blacklisted(env: Env, token: Address, investor: Address) -> bool {
-        let key = DataKey::Blacklist(token);
-        env.storage()
-            .persistent()
-            .get::<DataKey, Map<Address, bool>>(&key)
-            .map(|m| m.get(investor).unwrap_or(false))
-            .unwrap_or(false)
+    /// Return a page of `token`'s blacklisted addresses, expired entries included, and
+    /// the next cursor. Limit capped at MAX_PAGE_LIMIT (20).
+    /// Ordering: by insertion order (#38). A page covers `limit` indices, so entries
+    /// removed since leave it shorter, but cursors stay valid across removals.
+    pub fn get_blacklist_page(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        Self::list_page(&env, AddressList::Blacklist, &token, start, limit)
     }
 
-    /// Return all blacklisted addresses for `token`'s offering.
-    /// Ordering: by insertion order, deterministic and stable across calls (#38).
-    pub fn get_blacklist(env: Env, token: Address) -> Vec<Address> {
-        let order_key = DataKey::BlacklistOrder(token);
-        env.storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&order_key)
-            .unwrap_or_else(|| Vec::new(&env))
+    /// Return `investor`'s blacklist record for `token`, or `None` if not listed. An
+    /// entry listed without a record gets reason 0, no evidence, the contract as
+    /// authority (unknown), `added_at` 0 and no expiry.
+    fn blacklist_record(env: &Env, token: &Address, investor: &Address) -> Option<BlacklistRecord> {
+        let record_key = DistributionKey::BlacklistRecord(token.clone(), investor.clone());
+        if let Some(record) = env.storage().persistent().get(&record_key) {
+            return Some(record);
+        }
+        let slot_key = AddressList::Blacklist.slot_key(token, investor);
+        let listed = env.storage().persistent().has(&slot_key)
+            || Self::legacy_listed(env, AddressList::Blacklist, token, investor);
+        listed.then(|| BlacklistRecord {
+            investor: investor.clone(),
+            reason: 0,
+            evidence_hash: BytesN::from_array(env, &[0; 32]),
+            authority: env.current_contract_address(),
+            added_at: 0,
+            expires_at: None,
+        })
     }
 
-    // ── Whitelist management ──────────────────────────────────
+    /// Return the number of blacklist entries for `token` and their hash.
+    pub fn get_blacklist_digest(env: Env, token: Address) -> ListDigest {
+        Self::list_digest(&env, AddressList::Blacklist, &token)
+    }
 
-    /// Set per-offering concentration limit. Caller must be the offering issuer.
-    /// `max_bps`: max allowed single-holder share in basis points (0 = disable).
-    /// `enforce`: if true, report_revenue will fail when reported concentration exceeds max_bps.
-    pub fn set_concentration_limit(
+    /// Move up to `limit` (capped at MAX_PAGE_LIMIT) of `token`'s blacklist and
+    /// whitelist entries out of the whole-list storage used before per-address keys,
+    /// blacklist first and in insertion order. Until then they still count for
+    /// `is_blacklisted`, `is_whitelisted` and `is_whitelist_enabled`, but are left
+    /// out of pages and digests. Issuer or admin.
+    ///
+    /// ### Returns
+    /// - `Ok(true)` once no legacy entries remain, `Ok(false)` if more calls are needed.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the admin.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn migrate_legacy_lists(
         env: Env,
-        issuer: Address,
+        caller: Address,
         token: Address,
-        max_bps: u32,
-        enforce: bool,
-    ) -> Result<(), RevoraError> {
+        limit: u32,
+    ) -> Result<bool, RevoraError> {
         Self::require_not_frozen(&env)?;
-        issuer.require_auth();
-        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
-            return Err(RevoraError::LimitReached); // reuse: "offering not found" semantics
-        }
-        let key = DataKey::ConcentrationLimit(issuer, token);
-        env.storage().persistent().set(&key, &ConcentrationLimitConfig { max_bps, enforce });
-        Ok(())
-    /// Add `investor` to the per-offering whitelist for `token`.
-    ///
-    /// Idempotent — calling with an already-whitelisted address is safe.
-    /// When a whitelist exists (non-empty), only whitelisted addresses
-    /// are eligible for revenue distribution (subject to blacklist override).
-    pub fn whitelist_add(env: Env, caller: Address, token: Address, investor: Address) {
+        Self::require_not_paused(&env);
         caller.require_auth();
+        Self::require_issuer_or_admin(&env, &caller, &token)?;
 
-        let key = DataKey::Whitelist(token.clone());
-        let mut map: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Map::new(&env));
-
-        map.set(investor.clone(), true);
-        env.storage().persistent().set(&key, &map);
+        let effective_limit =
+            if limit == 0 || limit > MAX_PAGE_LIMIT { MAX_PAGE_LIMIT } else { limit };
+        let mut moved = 0u32;
+        let mut done = true;
+        for list in [AddressList::Blacklist, AddressList::Whitelist] {
+            let key = list.legacy_key(&token);
+            let Some(mut map) = env.storage().persistent().get::<_, Map<Address, bool>>(&key)
+            else {
+                continue;
+            };
+            let mut order = Self::legacy_order(&env, list, &token, &map);
+            while moved < effective_limit {
+                let Some(addr) = order.pop_front() else {
+                    break;
+                };
+                if map.get(addr.clone()).unwrap_or(false) {
+                    Self::list_insert(&env, list, &token, &addr);
+                }
+                map.remove(addr);
+                moved += 1;
+            }
+            Self::store_legacy_list(&env, list, &token, &map, &order);
+            done = done && map.is_empty();
+        }
 
-        env.events().publish((EVENT_WL_ADD, token, caller), investor);
+        env.events().publish((EVENT_LIST_MIGRATED, token, caller), (moved, done));
+        Ok(done)
     }
 
-    /// Remove `investor` from the per-offering whitelist for `token`.
-    ///
-    /// Idempotent — calling when the address is not listed is safe.
-    pub fn whitelist_remove(env: Env, caller: Address, token: Address, investor: Address) {
-        caller.require_auth();
+    fn list_digest(env: &Env, list: AddressList, token: &Address) -> ListDigest {
+        Self::stored_digest(env, &list.digest_key(token))
+    }
 
-        let key = DataKey::Whitelist(token.clone());
-        let mut map: Map<Address, bool> = env
-            .storage()
+    fn stored_digest(env: &Env, key: &DistributionKey) -> ListDigest {
+        env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Map::new(&env));
+            .get(key)
+            .unwrap_or(ListDigest { count: 0, hash: BytesN::from_array(env, &[0; 32]) })
+    }
 
-        map.remove(investor.clone());
-        env.storage().persistent().set(&key, &map);
+    /// Count `addr` into the digest stored at `key`, or out of it if `added` is false.
+    fn update_digest(env: &Env, key: &DistributionKey, addr: &Address, added: bool) {
+        let mut digest = Self::stored_digest(env, key);
+        if added {
+            digest.count += 1;
+        } else {
+            digest.count -= 1;
+        }
+        digest.hash = Self::toggle_list_hash(env, &digest.hash, addr);
+        env.storage().persistent().set(key, &digest);
+    }
 
-        env.events().publish((EVENT_WL_REM, token, caller), investor);
+    /// Append `addr` to `list`. Returns false if it was already listed.
+    fn list_insert(env: &Env, list: AddressList, token: &Address, addr: &Address) -> bool {
+        let slot_key = list.slot_key(token, addr);
+        if env.storage().persistent().has(&slot_key) {
+            return false;
+        }
+        let index = Self::list_end(env, list, token);
+        env.storage().persistent().set(&list.at_key(token, index), addr);
+        env.storage().persistent().set(&slot_key, &index);
+        env.storage().persistent().set(&list.end_key(token), &(index + 1));
+        Self::update_digest(env, &list.digest_key(token), addr, true);
+        true
     }
 
-    /// Returns `true` if `investor` is whitelisted for `token`'s offering.
-    ///
-    /// Note: If the whitelist is empty (disabled), this returns `false`.
-    /// Use `is_whitelist_enabled` to check if whitelist enforcement is active.
-    pub fn is_whitelisted(env: Env, token: Address, investor: Address) -> bool {
-        let key = DataKey::Whitelist(token);
-        env.storage()
-            .persistent()
-            .get::<DataKey, Map<Address, bool>>(&key)
-            .map(|m| m.get(investor).unwrap_or(false))
-            .unwrap_or(false)
+    /// Remove `addr` from `list`, leaving a gap at its index so later entries keep
+    /// theirs. Returns false if it was not listed.
+    fn list_remove(env: &Env, list: AddressList, token: &Address, addr: &Address) -> bool {
+        let removed_legacy = Self::legacy_list_remove(env, list, token, addr);
+        let slot_key = list.slot_key(token, addr);
+        let Some(index) = env.storage().persistent().get::<_, u32>(&slot_key) else {
+            return removed_legacy;
+        };
+        env.storage().persistent().remove(&list.at_key(token, index));
+        env.storage().persistent().remove(&slot_key);
+        Self::update_digest(env, &list.digest_key(token), addr, false);
+        true
+    }
+
+    /// Index the next entry of `list` gets.
+    fn list_end(env: &Env, list: AddressList, token: &Address) -> u32 {
+        env.storage().persistent().get(&list.end_key(token)).unwrap_or(0)
     }
 
-    /// Return all whitelisted addresses for `token`'s offering.
-    pub fn get_whitelist(env: Env, token: Address) -> Vec<Address> {
-        let key = DataKey::Whitelist(token);
+    /// Whether `addr` is in `list`'s legacy whole-list map.
+    fn legacy_listed(env: &Env, list: AddressList, token: &Address, addr: &Address) -> bool {
         env.storage()
             .persistent()
-            .get::<DataKey, Map<Address, bool>>(&key)
-            .map(|m| m.keys())
-            .unwrap_or_else(|| Vec::new(&env))
+            .get::<_, Map<Address, bool>>(&list.legacy_key(token))
+            .is_some_and(|map| map.get(addr.clone()).unwrap_or(false))
     }
 
-    /// Returns `true` if whitelist enforcement is enabled for `token`'s offering.
-    ///
-    /// Whitelist is considered enabled when it contains at least one address.
-    /// When disabled (empty), all non-blacklisted holders are eligible.
-    pub fn is_whitelist_enabled(env: Env, token: Address) -> bool {
-        Self::get_whitelist(env, token).len() > 0
-    // ── Holder concentration guardrail (#26) ───────────────────
+    /// Take `addr` out of `list`'s legacy map and, for the blacklist, its legacy order.
+    /// Returns false if it was not in the map.
+    fn legacy_list_remove(env: &Env, list: AddressList, token: &Address, addr: &Address) -> bool {
+        let key = list.legacy_key(token);
+        let Some(mut map) = env.storage().persistent().get::<_, Map<Address, bool>>(&key) else {
+            return false;
+        };
+        if map.remove(addr.clone()).is_none() {
+            return false;
+        }
+        let mut order = Self::legacy_order(env, list, token, &map);
+        if let Some(i) = order.first_index_of(addr) {
+            order.remove(i);
+        }
+        Self::store_legacy_list(env, list, token, &map, &order);
+        true
+    }
 
-    /// Set the concentration limit for an offering.
-    ///
-    /// Configures the maximum share a single holder can own and whether it is enforced.
-    ///
-    /// ### Parameters
-    /// - `issuer`: The offering issuer. Must provide authentication.
-    /// - `token`: The token representing the offering.
-    /// - `max_bps`: The maximum allowed single-holder share in basis points (0-10000, 0 = disabled).
-    /// - `enforce`: If true, `report_revenue` will fail if current concentration exceeds `max_bps`.
+    /// Legacy entries of `list` in the order `migrate_legacy_lists` moves them: the
+    /// blacklist's insertion order, or the map's own order for the whitelist.
+    fn legacy_order(
+        env: &Env,
+        list: AddressList,
+        token: &Address,
+        map: &Map<Address, bool>,
+    ) -> Vec<Address> {
+        let order = match list {
+            AddressList::Blacklist => {
+                env.storage().persistent().get(&DataKey::BlacklistOrder(token.clone()))
+            }
+            AddressList::Whitelist => None,
+        };
+        order.unwrap_or_else(|| map.keys())
+    }
+
+    /// Write back `list`'s legacy map and order, removing both once the map is empty.
+    fn store_legacy_list(
+        env: &Env,
+        list: AddressList,
+        token: &Address,
+        map: &Map<Address, bool>,
+        order: &Vec<Address>,
+    ) {
+        let key = list.legacy_key(token);
+        let order_key = DataKey::BlacklistOrder(token.clone());
+        if map.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, map);
+        }
+        if let AddressList::Blacklist = list {
+            if map.is_empty() || order.is_empty() {
+                env.storage().persistent().remove(&order_key);
+            } else {
+                env.storage().persistent().set(&order_key, order);
+            }
+        }
+    }
+
+    /// XOR `sha256(xdr(addr))` into `hash`, which adds `addr` to a list hash or takes
+    /// it out again.
+    fn toggle_list_hash(env: &Env, hash: &BytesN<32>, addr: &Address) -> BytesN<32> {
+        let addr_hash = env.crypto().sha256(&addr.clone().to_xdr(env)).to_array();
+        let mut bytes = hash.to_array();
+        for (byte, addr_byte) in bytes.iter_mut().zip(addr_hash.iter()) {
+            *byte ^= addr_byte;
+        }
+        BytesN::from_array(env, &bytes)
+    }
+
+    fn list_page(
+        env: &Env,
+        list: AddressList,
+        token: &Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        let count = Self::list_end(env, list, token);
+        Self::indexed_page(env, count, start, limit, |i| list.at_key(token, i))
+    }
+
+    /// Page over the addresses stored at `at(i)` for indices below `count`.
+    fn indexed_page(
+        env: &Env,
+        count: u32,
+        start: u32,
+        limit: u32,
+        at: impl Fn(u32) -> DistributionKey,
+    ) -> (Vec<Address>, Option<u32>) {
+        let effective_limit =
+            if limit == 0 || limit > MAX_PAGE_LIMIT { MAX_PAGE_LIMIT } else { limit };
+
+        if start >= count {
+            return (Vec::new(env), None);
+        }
+
+        // Scans `effective_limit` indices, so gaps left by removals shorten the page.
+        let end = core::cmp::min(start + effective_limit, count);
+        let mut results = Vec::new(env);
+        for i in start..end {
+            if let Some(addr) = env.storage().persistent().get(&at(i)) {
+                results.push_back(addr);
+            }
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    // ── Platform sanctions ────────────────────────────────────
+
+    /// Set or clear the compliance address, which may manage the platform-wide sanctions
+    /// list alongside the admin. Admin only.
     ///
     /// ### Returns
     /// - `Ok(())` on success.
-    /// - `Err(RevoraError::LimitReached)` if the offering is not found.
+    /// - `Err(RevoraError::NotInitialized)` if no admin is set.
     /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
-    pub fn set_concentration_limit(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        max_bps: u32,
-        enforce: bool,
-    ) -> Result<(), RevoraError> {
+    pub fn set_compliance(env: Env, compliance: Option<Address>) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
+        let admin = Self::get_admin(env.clone()).ok_or(RevoraError::NotInitialized)?;
+        admin.require_auth();
 
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::LimitReached)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::LimitReached);
-        }
-
-        if !Self::is_event_only(&env) {
-            issuer.require_auth();
-            let key = DataKey::ConcentrationLimit(issuer, token);
-            env.storage()
-                .persistent()
-                .set(&key, &ConcentrationLimitConfig { max_bps, enforce });
+        match &compliance {
+            Some(addr) => env.storage().persistent().set(&DistributionKey::Compliance, addr),
+            None => env.storage().persistent().remove(&DistributionKey::Compliance),
         }
+        env.events().publish((EVENT_COMPLIANCE_SET, admin), compliance);
         Ok(())
     }
 
-    /// Report the current top-holder concentration for an offering.
-    ///
-    /// Stores the provided concentration value. If it exceeds the configured limit,
-    /// a `conc_warn` event is emitted. The stored value is used for enforcement in `report_revenue`.
+    /// Return the compliance address, if one is set.
+    pub fn get_compliance(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DistributionKey::Compliance)
+    }
+
+    /// Add `investor` to the platform-wide sanctions list. Sanctioned addresses are not
+    /// paid by any offering and cannot be assigned a share (see `require_payout_eligible`
+    /// for how this combines with the per-offering lists). Their revenue is settled
+    /// under each offering's blacklist policy by `settle_blacklisted_revenue`. Idempotent.
     ///
-    /// ### Parameters
-    /// - `issuer`: The offering issuer. Must provide authentication.
-    /// - `token`: The token representing the offering.
-    /// - `concentration_bps`: The current top-holder share in basis points.
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the admin nor the
+    ///   compliance address.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn sanction_add(env: Env, caller: Address, investor: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        Self::require_admin_or_compliance(&env, &caller)?;
+
+        Self::sanctions_insert(&env, &investor);
+
+        let digest = Self::stored_digest(&env, &DistributionKey::SanctionsDigest);
+        env.events().publish((EVENT_SANCTION_ADD, caller), (investor, digest));
+        Ok(())
+    }
+
+    /// Remove `investor` from the platform-wide sanctions list. Revenue that accrued
+    /// while they were sanctioned stays claimable, and revenue escrowed for them is paid
+    /// out in up to MAX_PAGE_LIMIT (20) offerings that now find them eligible; the
+    /// rest via `claim_escrow`. Idempotent.
     ///
     /// ### Returns
     /// - `Ok(())` on success.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the admin nor the
+    ///   compliance address.
     /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
-    pub fn report_concentration(
+    pub fn sanction_remove(
         env: Env,
-        issuer: Address,
-        token: Address,
-        concentration_bps: u32,
+        caller: Address,
+        investor: Address,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
-        issuer.require_auth();
-        let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
-        env.storage().persistent().set(&curr_key, &concentration_bps);
-
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::OfferingNotFound);
-        }
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        Self::require_admin_or_compliance(&env, &caller)?;
 
-        if !Self::is_event_only(&env) {
-            issuer.require_auth();
-            let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
-            env.storage()
+        if Self::sanctions_remove(&env, &investor) {
+            let escrowed: Vec<Address> = env
+                .storage()
                 .persistent()
-                .set(&curr_key, &concentration_bps);
-        }
-
-        let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
-        if let Some(config) =
-            env.storage().persistent().get::<DataKey, ConcentrationLimitConfig>(&limit_key)
-        {
-            if config.max_bps > 0 && concentration_bps > config.max_bps {
-                env.events().publish(
-                    (EVENT_CONCENTRATION_WARNING, issuer, token),
-                    (concentration_bps, config.max_bps),
-                );
+                .get(&AccountingKey::EscrowedTokens(investor.clone()))
+                .unwrap_or_else(|| Vec::new(&env));
+            let end = core::cmp::min(escrowed.len(), MAX_PAGE_LIMIT);
+            for token in escrowed.slice(0..end).iter() {
+                Self::release_escrow(&env, &token, &investor);
             }
         }
+
+        let digest = Self::stored_digest(&env, &DistributionKey::SanctionsDigest);
+        env.events().publish((EVENT_SANCTION_REM, caller), (investor, digest));
         Ok(())
     }
 
-    /// Get concentration limit config for an offering.
+    /// Returns `true` if `investor` is on the platform-wide sanctions list.
+    pub fn is_sanctioned(env: Env, investor: Address) -> bool {
+        env.storage().persistent().has(&DistributionKey::SanctionsSlot(investor))
+    }
+
+    /// Return a page of sanctioned addresses and the next cursor. Limit capped at
+    /// MAX_PAGE_LIMIT (20).
+    /// Ordering: by insertion order; pages behave as in `get_blacklist_page`.
+    pub fn get_sanctions_page(env: Env, start: u32, limit: u32) -> (Vec<Address>, Option<u32>) {
+        let count = Self::sanctions_end(&env);
+        Self::indexed_page(&env, count, start, limit, DistributionKey::SanctionsAt)
+    }
+
+    /// Return the number of sanctioned addresses and their hash.
+    pub fn get_sanctions_digest(env: Env) -> ListDigest {
+        Self::stored_digest(&env, &DistributionKey::SanctionsDigest)
+    }
+
+    /// Append `addr` to the sanctions list. Returns false if it was already listed.
+    fn sanctions_insert(env: &Env, addr: &Address) -> bool {
+        let slot_key = DistributionKey::SanctionsSlot(addr.clone());
+        if env.storage().persistent().has(&slot_key) {
+            return false;
+        }
+        let index = Self::sanctions_end(env);
+        env.storage().persistent().set(&DistributionKey::SanctionsAt(index), addr);
+        env.storage().persistent().set(&slot_key, &index);
+        env.storage().persistent().set(&DistributionKey::SanctionsEnd, &(index + 1));
+        Self::update_digest(env, &DistributionKey::SanctionsDigest, addr, true);
+        true
+    }
+
+    /// Remove `addr` from the sanctions list, leaving a gap at its index. Returns false
+    /// if it was not listed.
+    fn sanctions_remove(env: &Env, addr: &Address) -> bool {
+        let slot_key = DistributionKey::SanctionsSlot(addr.clone());
+        let Some(index) = env.storage().persistent().get::<_, u32>(&slot_key) else {
+            return false;
+        };
+        env.storage().persistent().remove(&DistributionKey::SanctionsAt(index));
+        env.storage().persistent().remove(&slot_key);
+        Self::update_digest(env, &DistributionKey::SanctionsDigest, addr, false);
+        true
+    }
+
+    /// Index the next sanctioned address gets.
+    fn sanctions_end(env: &Env) -> u32 {
+        env.storage().persistent().get(&DistributionKey::SanctionsEnd).unwrap_or(0)
+    }
+
+    /// `caller` must be the contract admin or the compliance address.
+    fn require_admin_or_compliance(env: &Env, caller: &Address) -> Result<(), RevoraError> {
+        let caller = Some(caller.clone());
+        if caller != Self::get_admin(env.clone()) && caller != Self::get_compliance(env.clone()) {
+            return Err(RevoraError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    // ── Whitelist management ──────────────────────────────────
+
+    /// Add `investor` to the per-offering whitelist for `token`.
+    ///
+    /// Idempotent — calling with an already-whitelisted address is safe.
+    /// When a whitelist exists (non-empty), only whitelisted addresses
+    /// are eligible for revenue distribution (subject to blacklist override).
+    /// Pays out the investor's escrowed revenue if this makes them eligible again.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the admin.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn whitelist_add(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        Self::require_issuer_or_admin(&env, &caller, &token)?;
+
+        if !Self::is_event_only(&env) {
+            Self::list_insert(&env, AddressList::Whitelist, &token, &investor);
+            Self::release_escrow(&env, &token, &investor);
+        }
+
+        let digest = Self::list_digest(&env, AddressList::Whitelist, &token);
+        env.events().publish((EVENT_WL_ADD, token, caller), (investor, digest));
+        Ok(())
+    }
+
+    /// Remove `investor` from the per-offering whitelist for `token`.
+    ///
+    /// Idempotent — calling when the address is not listed is safe.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the admin.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn whitelist_remove(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        Self::require_issuer_or_admin(&env, &caller, &token)?;
+
+        if !Self::is_event_only(&env) {
+            Self::list_remove(&env, AddressList::Whitelist, &token, &investor);
+        }
+
+        let digest = Self::list_digest(&env, AddressList::Whitelist, &token);
+        env.events().publish((EVENT_WL_REM, token, caller), (investor, digest));
+        Ok(())
+    }
+
+    /// `caller` must be the offering's current issuer or the contract admin.
+    /// In event-only mode offerings are not persisted, so only the admin qualifies.
+    fn require_issuer_or_admin(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        if Self::is_event_only(env) {
+            return match Self::get_admin(env.clone()) {
+                Some(admin) if admin == *caller => Ok(()),
+                _ => Err(RevoraError::NotAuthorized),
+            };
+        }
+        let current_issuer =
+            Self::get_current_issuer(env, token).ok_or(RevoraError::OfferingNotFound)?;
+        let admin = Self::get_admin(env.clone());
+        if *caller != current_issuer && Some(caller.clone()) != admin {
+            return Err(RevoraError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if `investor` is whitelisted for `token`'s offering.
+    ///
+    /// Note: If the whitelist is empty (disabled), this returns `false`.
+    /// Use `is_whitelist_enabled` to check if whitelist enforcement is active.
+    pub fn is_whitelisted(env: Env, token: Address, investor: Address) -> bool {
+        let slot_key = AddressList::Whitelist.slot_key(&token, &investor);
+        env.storage().persistent().has(&slot_key)
+            || Self::legacy_listed(&env, AddressList::Whitelist, &token, &investor)
+    }
+
+    /// Return a page of `token`'s whitelisted addresses and the next cursor. Limit
+    /// capped at MAX_PAGE_LIMIT (20).
+    /// Ordering: by insertion order; pages behave as in `get_blacklist_page`.
+    pub fn get_whitelist_page(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        Self::list_page(&env, AddressList::Whitelist, &token, start, limit)
+    }
+
+    /// Return the number of whitelisted addresses for `token` and their hash.
+    pub fn get_whitelist_digest(env: Env, token: Address) -> ListDigest {
+        Self::list_digest(&env, AddressList::Whitelist, &token)
+    }
+
+    /// Returns `true` if whitelist enforcement is enabled for `token`'s offering.
+    ///
+    /// Whitelist is considered enabled when it contains at least one address.
+    /// When disabled (empty), all non-blacklisted holders are eligible.
+    pub fn is_whitelist_enabled(env: Env, token: Address) -> bool {
+        Self::list_digest(&env, AddressList::Whitelist, &token).count > 0
+            || env.storage().persistent().has(&DataKey::Whitelist(token))
+    }
+
+    // ── Holder concentration guardrail (#26) ───────────────────
+
+    /// Set the concentration limit for an offering.
+    ///
+    /// Configures the maximum share a single holder can own and whether it is enforced.
+    ///
+    /// ### Parameters
+    /// - `issuer`: The offering issuer. Must provide authentication.
+    /// - `token`: The token representing the offering.
+    /// - `max_bps`: The maximum allowed single-holder share in basis points (0-10000, 0 = disabled).
+    /// - `enforce`: If true, `report_revenue` will fail if current concentration exceeds `max_bps`.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::LimitReached)` if the offering is not found.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_concentration_limit(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        max_bps: u32,
+        enforce: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::LimitReached)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::LimitReached);
+        }
+
+        if !Self::is_event_only(&env) {
+            issuer.require_auth();
+            let key = DataKey::ConcentrationLimit(issuer, token);
+            env.storage()
+                .persistent()
+                .set(&key, &ConcentrationLimitConfig { max_bps, enforce });
+        }
+        Ok(())
+    }
+
+    /// Report the current top-holder concentration for an offering.
+    ///
+    /// Stores the provided concentration value. If it exceeds the configured limit,
+    /// a `conc_warn` event is emitted. The stored value is used for enforcement in `report_revenue`.
+    ///
+    /// ### Parameters
+    /// - `issuer`: The offering issuer. Must provide authentication.
+    /// - `token`: The token representing the offering.
+    /// - `concentration_bps`: The current top-holder share in basis points.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn report_concentration(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        concentration_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        if !Self::is_event_only(&env) {
+            issuer.require_auth();
+            let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
+            env.storage().persistent().set(&curr_key, &concentration_bps);
+        }
+
+        let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
+        if let Some(config) =
+            env.storage().persistent().get::<DataKey, ConcentrationLimitConfig>(&limit_key)
+        {
+            if config.max_bps > 0 && concentration_bps > config.max_bps {
+                env.events().publish(
+                    (EVENT_CONCENTRATION_WARNING, issuer, token),
+                    (concentration_bps, config.max_bps),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Get concentration limit config for an offering.
     pub fn get_concentration_limit(
         env: Env,
         issuer: Address,
@@ -1459,12 +2303,14 @@ impl RevoraRevenueShare {
 
     /// Set the rounding mode for an offering's share calculations.
     ///
-    /// The rounding mode determines how fractional payouts are handled.
+    /// The rounding mode determines how fractional payouts are handled. Whether a
+    /// period can round up is decided when it is deposited: if rounding every holder
+    /// up could exceed the deposit, the whole period pays truncated.
     ///
     /// ### Parameters
     /// - `issuer`: The offering issuer. Must provide authentication.
     /// - `token`: The token representing the offering.
-    /// - `mode`: The rounding mode to use (`RoundingMode::Truncation` or `RoundingMode::RoundHalfUp`).
+    /// - `mode`: The rounding mode to use (see `RoundingMode`).
     ///
     /// ### Returns
     /// - `Ok(())` on success.
@@ -1544,25 +2390,33 @@ impl RevoraRevenueShare {
     /// Compute share of `amount` at `revenue_share_bps` using the given rounding mode.
     /// Guarantees: result between 0 and amount (inclusive); no loss of funds when summing shares if caller uses same mode.
     pub fn compute_share(
-        _env: Env,
+        env: Env,
         amount: i128,
         revenue_share_bps: u32,
         mode: RoundingMode,
     ) -> i128 {
-        if revenue_share_bps > 10_000 {
+        Self::compute_share_units(env, amount, revenue_share_bps as i128, SharePrecision::Bps, mode)
+    }
+
+    /// Compute share of `amount` for `share` units at `precision` (e.g. 1 = 1 ppm).
+    /// Same guarantees as `compute_share`; 0 if `share` is negative or above 100%.
+    pub fn compute_share_units(
+        _env: Env,
+        amount: i128,
+        share: i128,
+        precision: SharePrecision,
+        mode: RoundingMode,
+    ) -> i128 {
+        Self::share_of(amount, share, Self::precision_scale(precision), mode)
+    }
+
+    /// `compute_share` math for `share` out of `scale` units.
+    fn share_of(amount: i128, share: i128, scale: i128, mode: RoundingMode) -> i128 {
+        if !(0..=scale).contains(&share) {
             return 0;
         }
-        let bps = revenue_share_bps as i128;
-        let raw = amount.checked_mul(bps).unwrap_or(0);
-        let share = match mode {
-            RoundingMode::Truncation => raw.checked_div(10_000).unwrap_or(0),
-            RoundingMode::RoundHalfUp => {
-                let half = 5_000_i128;
-                let adjusted =
-                    if raw >= 0 { raw.saturating_add(half) } else { raw.saturating_sub(half) };
-                adjusted.checked_div(10_000).unwrap_or(0)
-            }
-        };
+        // Cannot fail: with share <= scale the result is at most |amount|
+        let share = Self::mul_div_rounded(amount, share, scale, mode).unwrap_or(0);
         // Clamp to [min(0, amount), max(0, amount)] to avoid overflow semantics affecting bounds
         let lo = core::cmp::min(0, amount);
         let hi = core::cmp::max(0, amount);
@@ -1575,7 +2429,8 @@ impl RevoraRevenueShare {
     ///
     /// Transfers `amount` of `payment_token` from `issuer` to the contract.
     /// The payment token is locked per offering on the first deposit; subsequent
-    /// deposits must use the same payment token.
+    /// deposits must use the same payment token. The platform fee is deducted and
+    /// accrued per payment token; holders' shares apply to the net amount.
     ///
     /// ### Parameters
     /// - `issuer`: The offering issuer. Must provide authentication.
@@ -1608,23 +2463,23 @@ impl RevoraRevenueShare {
             return Err(RevoraError::OfferingNotFound);
         }
 
+        issuer.require_auth();
 
         Self::do_deposit_revenue(&env, issuer, token, payment_token, amount, period_id)
     }
 
-        // Verify offering exists
-        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
-            .ok_or(RevoraError::OfferingNotFound)?;
-        if offering.payout_asset != payment_token {
-            return Err(RevoraError::PayoutAssetMismatch);
-        }
-
-
     /// Deposit revenue for an offering using a specific snapshot reference.
     ///
     /// Requires that snapshot distribution is enabled for the offering.
     /// The `snapshot_reference` (e.g., ledger sequence) must be strictly greater than
     /// any previously recorded snapshot for this offering to prevent duplication.
+    ///
+    /// It must not be ahead of the current ledger sequence (`SnapshotInFuture`).
+    ///
+    /// The period is bound to the snapshot: each holder is entitled to
+    /// `revenue * revenue_share_bps / 10000 * balance_at(holder, snapshot_reference) / total_supply_at(snapshot_reference)`,
+    /// read from the offering token (see `SnapshotToken`), instead of their `HolderShare`.
+    /// Not available on accumulator-mode offerings (`DistributionModeConflict`).
     pub fn deposit_revenue_with_snapshot(
         env: Env,
         issuer: Address,
@@ -1640,15 +2495,6 @@ impl RevoraRevenueShare {
         // 1. Verify snapshots are enabled
         if !Self::get_snapshot_config(env.clone(), issuer.clone(), token.clone()) {
             return Err(RevoraError::SnapshotNotEnabled);
-        issuer.require_auth();
-
-        Self::require_positive_amount(amount)?;
-        Self::require_valid_period_id(period_id)?;
-
-        // Check period not already deposited
-        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-        if env.storage().persistent().has(&rev_key) {
-            return Err(RevoraError::PeriodAlreadyDeposited);
         }
 
         // 2. Validate snapshot reference (must be strictly monotonic)
@@ -1657,8 +2503,18 @@ impl RevoraRevenueShare {
         if snapshot_reference <= last_snap {
             return Err(RevoraError::OutdatedSnapshot);
         }
+        if snapshot_reference > env.ledger().sequence() as u64 {
+            return Err(RevoraError::SnapshotInFuture);
+        }
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
 
-        // 3. Delegate to core deposit logic
+        // 3. Delegate to core deposit logic; the snapshot is stored first so the deposit
+        // does not take a TWAB window
+        env.storage()
+            .persistent()
+            .set(&DistributionKey::PeriodSnapshot(token.clone(), period_id), &snapshot_reference);
         Self::do_deposit_revenue(
             &env,
             issuer.clone(),
@@ -1667,11 +2523,16 @@ impl RevoraRevenueShare {
             amount,
             period_id,
         )?;
+        env.storage().persistent().set(&DistributionKey::SnapshotBound(token.clone()), &true);
+        // Snapshot holders are not in the holder registry, so rounding cannot be bounded
+        if Self::rounds_up(Self::offering_rounding_mode(&env, &token)) {
+            env.storage()
+                .persistent()
+                .set(&DistributionKey::PeriodTruncated(token.clone(), period_id), &true);
+        }
 
         // 4. Update last snapshot and emit specialized event
-        env.storage()
-            .persistent()
-            .set(&snap_key, &snapshot_reference);
+        env.storage().persistent().set(&snap_key, &snapshot_reference);
         env.events().publish(
             (EVENT_REV_DEP_SNAP, issuer, token),
             (payment_token, amount, period_id, snapshot_reference),
@@ -1689,65 +2550,2911 @@ impl RevoraRevenueShare {
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
         issuer.require_auth();
-        // Track total deposited revenue per offering (#39)
-        let deposited_key = DataKey::DepositedRevenue(token.clone());
-        let total_deposited: i128 = env.storage().persistent().get(&deposited_key).unwrap_or(0);
-        env.storage()
-            .persistent()
-            .set(&deposited_key, &total_deposited.saturating_add(amount));
-
-        // Store deposit timestamp for time-delayed claims (#27)
-        let deposit_time = env.ledger().timestamp();
-        let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
-        env.storage().persistent().set(&time_key, &deposit_time);
-
         if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
             return Err(RevoraError::OfferingNotFound);
         }
+        let key = DataKey::SnapshotConfig(issuer.clone(), token.clone());
+        env.storage().persistent().set(&key, &enabled);
+
+        env.events().publish((EVENT_SNAP_CONFIG, issuer, token), enabled);
+        Ok(())
+    }
+
+    /// Check if snapshot-based distribution is enabled for an offering.
+    pub fn get_snapshot_config(env: Env, issuer: Address, token: Address) -> bool {
+        let key = DataKey::SnapshotConfig(issuer, token);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Get the latest recorded snapshot reference for an offering.
+    pub fn get_last_snapshot_ref(env: Env, issuer: Address, token: Address) -> u64 {
+        let key = DataKey::LastSnapshotRef(issuer, token);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Return the snapshot reference a period was deposited against, or `None` if the
+    /// period is paid by `HolderShare`.
+    pub fn get_period_snapshot(env: Env, token: Address, period_id: u64) -> Option<u64> {
+        env.storage().persistent().get(&DistributionKey::PeriodSnapshot(token, period_id))
+    }
+
+    /// Enable or disable time-weighted (TWAB) entitlement for an offering's deposits.
+    ///
+    /// While enabled, each deposit covers the window since the previous TWAB deposit
+    /// (or since enabling), and a holder's share of the period is their share averaged
+    /// over that window, so a share held only around the deposit earns little.
+    /// Averages come from observations recorded on every share change. Snapshot-bound
+    /// deposits keep using the snapshot.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::DistributionModeConflict)` on accumulator-mode offerings.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_twab_config(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        enabled: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+
+        env.storage().persistent().set(&DistributionKey::TwabConfig(token.clone()), &enabled);
+        if enabled {
+            let start_key = DistributionKey::TwabWindowStart(token.clone());
+            env.storage().persistent().set(&start_key, &env.ledger().timestamp());
+        }
+        env.events().publish((EVENT_TWAB_CONFIG, issuer, token), enabled);
+        Ok(())
+    }
+
+    /// Check if time-weighted entitlement is enabled for an offering.
+    pub fn get_twab_config(env: Env, token: Address) -> bool {
+        env.storage().persistent().get(&DistributionKey::TwabConfig(token)).unwrap_or(false)
+    }
+
+    /// Return the (start, end) timestamps a TWAB period averages over, or `None` if the
+    /// period was not deposited with TWAB enabled.
+    pub fn get_period_twab_window(env: Env, token: Address, period_id: u64) -> Option<(u64, u64)> {
+        env.storage().persistent().get(&DistributionKey::PeriodTwabWindow(token, period_id))
+    }
+
+    /// Return a holder's time-weighted share (in share units, truncated) over a TWAB
+    /// period's window, or `None` if the period has no TWAB window.
+    pub fn get_twab_share(
+        env: Env,
+        token: Address,
+        holder: Address,
+        period_id: u64,
+    ) -> Option<i128> {
+        let (start, end) = Self::get_period_twab_window(env.clone(), token.clone(), period_id)?;
+        let (share_seconds, duration) =
+            Self::time_weighted_share(&env, &token, &holder, start, end).ok()?;
+        Some(share_seconds / duration)
+    }
+
+    /// Return all TWAB observations for a holder, oldest first.
+    pub fn get_twab_observations(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Vec<TwabObservation> {
+        let count_key = DistributionKey::TwabObsCount(token.clone(), holder.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let mut observations = Vec::new(&env);
+        for i in 0..count {
+            let obs_key = DistributionKey::TwabObs(token.clone(), holder.clone(), i);
+            observations.push_back(env.storage().persistent().get(&obs_key).unwrap());
+        }
+        observations
+    }
+
+    /// Append an observation of the holder's new share at the current timestamp,
+    /// accruing the previous share up to now. Changes within the same second
+    /// collapse into one observation.
+    fn record_twab_observation(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        share: i128,
+    ) -> Result<(), RevoraError> {
+        let now = env.ledger().timestamp();
+        let count_key = DistributionKey::TwabObsCount(token.clone(), holder.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if count > 0 {
+            let last_key = DistributionKey::TwabObs(token.clone(), holder.clone(), count - 1);
+            let last: TwabObservation = env.storage().persistent().get(&last_key).unwrap();
+            if last.timestamp == now {
+                let obs = TwabObservation { share, ..last };
+                env.storage().persistent().set(&last_key, &obs);
+                return Ok(());
+            }
+        }
+
+        let cumulative = Self::cumulative_share_at(env, token, holder, now)?;
+        let obs_key = DistributionKey::TwabObs(token.clone(), holder.clone(), count);
+        let obs = TwabObservation { timestamp: now, share, cumulative };
+        env.storage().persistent().set(&obs_key, &obs);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Ok(())
+    }
+
+    /// Share-seconds the holder accrued up to `timestamp`, from the last observation
+    /// at or before it (0 before the first observation).
+    fn cumulative_share_at(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        timestamp: u64,
+    ) -> Result<i128, RevoraError> {
+        match Self::twab_observation_at(env, token, holder, timestamp) {
+            Some(obs) => {
+                let held = obs
+                    .share
+                    .checked_mul((timestamp - obs.timestamp) as i128)
+                    .ok_or(RevoraError::ArithmeticOverflow)?;
+                obs.cumulative.checked_add(held).ok_or(RevoraError::ArithmeticOverflow)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Binary-search the holder's observations for the last one at or before `timestamp`.
+    fn twab_observation_at(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        timestamp: u64,
+    ) -> Option<TwabObservation> {
+        let count_key = DistributionKey::TwabObsCount(token.clone(), holder.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let (mut lo, mut hi) = (0_u32, count);
+        let mut found = None;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let obs_key = DistributionKey::TwabObs(token.clone(), holder.clone(), mid);
+            let obs: TwabObservation = env.storage().persistent().get(&obs_key).unwrap();
+            if obs.timestamp <= timestamp {
+                found = Some(obs);
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        found
+    }
+
+    /// The holder's share-seconds over `[start, end]` and the window length. An empty
+    /// window (two deposits in the same second) counts the share at `end` for 1 second.
+    fn time_weighted_share(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        start: u64,
+        end: u64,
+    ) -> Result<(i128, i128), RevoraError> {
+        if end <= start {
+            let share = Self::twab_observation_at(env, token, holder, end).map_or(0, |o| o.share);
+            return Ok((share, 1));
+        }
+        let share_seconds = Self::cumulative_share_at(env, token, holder, end)?
+            - Self::cumulative_share_at(env, token, holder, start)?;
+        Ok((share_seconds, (end - start) as i128))
+    }
+
+    /// Set a holder's revenue share (in basis points) for an offering.
+    ///
+    /// The share determines the percentage of a period's revenue the holder can claim.
+    /// On offerings with a finer `SharePrecision` it is converted to share units.
+    /// A holder's first assignment starts their entitlement at the current period count,
+    /// so earlier deposits are not claimable unless granted via `grant_back_periods`.
+    ///
+    /// ### Parameters
+    /// - `issuer`: The offering issuer. Must provide authentication.
+    /// - `token`: The token representing the offering.
+    /// - `holder`: The address of the token holder.
+    /// - `share_bps`: The holder's share in basis points (0-10000).
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::InvalidShareBps)` if `share_bps` exceeds 10000.
+    /// - `Err(RevoraError::ShareAllocationExceeded)` if all holder shares together would
+    ///   exceed the offering's `revenue_share_bps` (capped at 10000).
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_holder_share(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        let share = Self::bps_to_units(&env, &token, share_bps);
+        Self::do_set_holder_share(&env, issuer, token, holder, share)
+    }
+
+    /// Set a holder's share in the offering's share units (see `set_share_precision`).
+    ///
+    /// Same rules and errors as `set_holder_share`; `InvalidShareBps` if `share` is
+    /// negative or above 100% in the offering's precision. Both return
+    /// `DistributionModeConflict` once token hooks are enabled (see `enable_token_hooks`),
+    /// and `HolderSanctioned` for a non-zero share to a sanctioned holder.
+    pub fn set_holder_share_units(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        share: i128,
+    ) -> Result<(), RevoraError> {
+        Self::do_set_holder_share(&env, issuer, token, holder, share)
+    }
+
+    fn do_set_holder_share(
+        env: &Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        share: i128,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(env)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        issuer.require_auth();
+
+        if Self::has_token_hooks(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+        if !(0..=Self::share_scale(env, &token)).contains(&share) {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        if share > 0 && Self::is_sanctioned(env.clone(), holder.clone()) {
+            return Err(RevoraError::HolderSanctioned);
+        }
+
+        let previous = Self::get_holder_share_units(env.clone(), token.clone(), holder.clone());
+        let total = Self::total_allocated_units(env, &token);
+        // Clamped: shares set before the running total existed are not counted in it
+        let new_total = core::cmp::max(total - previous, 0) + share;
+        if new_total > Self::share_allocation_cap(env, &issuer, &token) {
+            return Err(RevoraError::ShareAllocationExceeded);
+        }
+        Self::set_total_allocated_units(env, &token, new_total);
+
+        if !Self::is_registered_holder(env, &token, &holder) {
+            Self::register_holder(env, &token, &holder);
+        }
+        if !Self::has_share_history(env, &token, &holder) {
+            let start_key = DataKey::EntitlementStart(token.clone(), holder.clone());
+            if !env.storage().persistent().has(&start_key) {
+                let period_count = Self::get_period_count(env.clone(), token.clone());
+                env.storage().persistent().set(&start_key, &period_count);
+            }
+        }
+
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            Self::settle_accumulator(env, &token, &holder)?;
+        }
+        Self::record_share_checkpoint(env, &token, &holder, share);
+        Self::record_twab_observation(env, &token, &holder, share)?;
+        Self::set_holder_share_value(env, &token, &holder, share);
+
+        let share_bps = Self::units_to_bps(env, &token, share);
+        env.events().publish((EVENT_SHARE_SET, issuer, token), (holder, share_bps, share));
+        Ok(())
+    }
+
+    /// Return a holder's share in basis points for an offering (0 if unset).
+    /// Finer-precision shares are truncated to whole bps.
+    pub fn get_holder_share(env: Env, token: Address, holder: Address) -> u32 {
+        let share = Self::get_holder_share_units(env.clone(), token.clone(), holder);
+        Self::units_to_bps(&env, &token, share)
+    }
+
+    /// Return a holder's share in the offering's share units (0 if unset).
+    pub fn get_holder_share_units(env: Env, token: Address, holder: Address) -> i128 {
+        Self::stored_holder_share(&env, &token, &holder).unwrap_or(0)
+    }
+
+    /// The holder's share in share units, read through from the bps key; `None` if it
+    /// was never set.
+    fn stored_holder_share(env: &Env, token: &Address, holder: &Address) -> Option<i128> {
+        let units_key = DistributionKey::HolderShareUnits(token.clone(), holder.clone());
+        if let Some(share) = env.storage().persistent().get(&units_key) {
+            return Some(share);
+        }
+        let key = DataKey::HolderShare(token.clone(), holder.clone());
+        let share_bps: Option<u32> = env.storage().persistent().get(&key);
+        share_bps.map(|share_bps| Self::bps_to_units(env, token, share_bps))
+    }
+
+    /// True if the offering's shares are stored in share units rather than bps: a finer
+    /// `SharePrecision`, or token balances under token hooks.
+    fn uses_share_units(env: &Env, token: &Address) -> bool {
+        Self::get_share_precision(env.clone(), token.clone()) != SharePrecision::Bps
+            || Self::has_token_hooks(env.clone(), token.clone())
+    }
+
+    fn set_holder_share_value(env: &Env, token: &Address, holder: &Address, share: i128) {
+        if Self::uses_share_units(env, token) {
+            let units_key = DistributionKey::HolderShareUnits(token.clone(), holder.clone());
+            env.storage().persistent().set(&units_key, &share);
+        } else {
+            let key = DataKey::HolderShare(token.clone(), holder.clone());
+            env.storage().persistent().set(&key, &(share as u32));
+        }
+    }
+
+    /// Sum of all holder shares in share units, read through from the bps total.
+    fn total_allocated_units(env: &Env, token: &Address) -> i128 {
+        let units_key = DistributionKey::TotalAllocatedUnits(token.clone());
+        if let Some(total) = env.storage().persistent().get(&units_key) {
+            return total;
+        }
+        let total_key = DataKey::TotalAllocatedBps(token.clone());
+        let total_bps: u32 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        Self::bps_to_units(env, token, total_bps)
+    }
+
+    fn set_total_allocated_units(env: &Env, token: &Address, total: i128) {
+        if Self::uses_share_units(env, token) {
+            env.storage()
+                .persistent()
+                .set(&DistributionKey::TotalAllocatedUnits(token.clone()), &total);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::TotalAllocatedBps(token.clone()), &(total as u32));
+        }
+    }
+
+    /// Return the sum of all holder shares (bps) currently allocated for an offering.
+    pub fn get_total_allocated_bps(env: Env, token: Address) -> u32 {
+        let total = Self::total_allocated_units(&env, &token);
+        Self::units_to_bps(&env, &token, total)
+    }
+
+    /// Return the bps still available to holders: the offering's `revenue_share_bps`
+    /// (capped at 10000) minus the allocated total. 0 if the offering is not found.
+    pub fn get_unallocated_bps(env: Env, token: Address) -> u32 {
+        match Self::get_current_issuer(&env, &token) {
+            Some(issuer) => {
+                let total = Self::total_allocated_units(&env, &token);
+                let cap = Self::share_allocation_cap(&env, &issuer, &token);
+                Self::units_to_bps(&env, &token, core::cmp::max(cap - total, 0))
+            }
+            None => 0,
+        }
+    }
+
+    /// The current offering's `revenue_share_bps`, clamped to 10000 (testnet mode
+    /// allows more). 0 if the offering is not found.
+    fn offering_share_bps(env: &Env, token: &Address) -> u32 {
+        Self::get_current_issuer(env, token)
+            .and_then(|issuer| Self::get_offering(env.clone(), issuer, token.clone()))
+            .map(|offering| core::cmp::min(offering.revenue_share_bps, 10_000))
+            .unwrap_or(0)
+    }
+
+    /// Upper bound (share units) for the sum of holder shares. Testnet mode allows
+    /// `revenue_share_bps` above 10000, so the cap is clamped to 100%.
+    fn share_allocation_cap(env: &Env, issuer: &Address, token: &Address) -> i128 {
+        Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .map(|offering| {
+                Self::bps_to_units(env, token, core::cmp::min(offering.revenue_share_bps, 10_000))
+            })
+            .unwrap_or(0)
+    }
+
+    /// Return the share (bps) that was in effect for the period at `period_index`
+    /// in deposit order. Used by `claim` so share changes are never retroactive.
+    pub fn get_holder_share_at(
+        env: Env,
+        token: Address,
+        holder: Address,
+        period_index: u32,
+    ) -> u32 {
+        let scale = Self::share_scale(&env, &token);
+        let share = Self::holder_share_at(&env, &token, &holder, period_index, scale);
+        Self::units_to_bps(&env, &token, share)
+    }
+
+    // ── Share precision ───────────────────────────────────────
+
+    /// Set the unit in which the offering's holder shares are expressed.
+    ///
+    /// Finer precisions allow allocations below 0.01%. Can only change before any
+    /// holder share is set or revenue deposited; accumulator mode requires bps.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::DistributionModeConflict)` if holders or deposits exist, or the
+    ///   offering uses accumulator mode.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_share_precision(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        precision: SharePrecision,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        if Self::get_holder_count(env.clone(), token.clone()) > 0
+            || Self::get_period_count(env.clone(), token.clone()) > 0
+            || Self::is_accumulator_mode(env.clone(), token.clone())
+        {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+
+        env.storage().persistent().set(&DistributionKey::SharePrecision(token.clone()), &precision);
+        env.events().publish((EVENT_SHARE_PRECISION, issuer, token), precision);
+        Ok(())
+    }
+
+    /// Return the offering's share precision (`Bps` if unset).
+    pub fn get_share_precision(env: Env, token: Address) -> SharePrecision {
+        let key = DistributionKey::SharePrecision(token);
+        env.storage().persistent().get(&key).unwrap_or(SharePrecision::Bps)
+    }
+
+    /// Number of share units that make up 100%.
+    fn precision_scale(precision: SharePrecision) -> i128 {
+        match precision {
+            SharePrecision::Bps => BPS_DENOMINATOR,
+            SharePrecision::Ppm => 1_000_000,
+            SharePrecision::Fixed18 => 1_000_000_000_000_000_000,
+        }
+    }
+
+    fn share_scale(env: &Env, token: &Address) -> i128 {
+        Self::precision_scale(Self::get_share_precision(env.clone(), token.clone()))
+    }
+
+    fn bps_to_units(env: &Env, token: &Address, bps: u32) -> i128 {
+        bps as i128 * (Self::share_scale(env, token) / BPS_DENOMINATOR)
+    }
+
+    fn units_to_bps(env: &Env, token: &Address, share: i128) -> u32 {
+        if Self::has_token_hooks(env.clone(), token.clone()) {
+            // Shares are token balances: report them as a fraction of the supply
+            let supply = Self::total_allocated_units(env, token);
+            if supply == 0 {
+                return 0;
+            }
+            let bps =
+                Self::mul_div_rounded(share, BPS_DENOMINATOR, supply, RoundingMode::Truncation);
+            // Cannot fail: the share is at most the supply
+            return bps.unwrap_or(0) as u32;
+        }
+        (share / (Self::share_scale(env, token) / BPS_DENOMINATOR)) as u32
+    }
+
+    /// Let a holder claim periods deposited before their share was first set.
+    ///
+    /// Moves the holder's entitlement start back to `from_period_index` (in deposit
+    /// order). Can be called before or after `set_holder_share`. Periods the holder
+    /// has already claimed past are not reopened.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::InvalidPeriodId)` if `from_period_index` is beyond the current period
+    ///   count or later than the holder's existing start.
+    /// - `Err(RevoraError::DistributionModeConflict)` if the offering uses accumulator mode.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn grant_back_periods(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        from_period_index: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+        if from_period_index > Self::get_period_count(env.clone(), token.clone()) {
+            return Err(RevoraError::InvalidPeriodId);
+        }
+        let start_key = DataKey::EntitlementStart(token.clone(), holder.clone());
+        if let Some(current) = env.storage().persistent().get::<_, u32>(&start_key) {
+            if from_period_index > current {
+                return Err(RevoraError::InvalidPeriodId);
+            }
+        }
+
+        env.storage().persistent().set(&start_key, &from_period_index);
+        env.events().publish((EVENT_BACK_PERIODS, issuer, token), (holder, from_period_index));
+        Ok(())
+    }
+
+    /// Return the first period index (in deposit order) the holder is entitled to claim.
+    pub fn get_entitlement_start(env: Env, token: Address, holder: Address) -> u32 {
+        let start_key = DataKey::EntitlementStart(token, holder);
+        env.storage().persistent().get(&start_key).unwrap_or(0)
+    }
+
+    /// Next period index to process for a holder: the later of their claim progress
+    /// and their entitlement start.
+    fn next_claim_index(env: &Env, token: &Address, holder: &Address) -> u32 {
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
+        let last_claimed: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+        core::cmp::max(
+            last_claimed,
+            Self::get_entitlement_start(env.clone(), token.clone(), holder.clone()),
+        )
+    }
+
+    /// Return all share checkpoints for a holder, oldest first.
+    pub fn get_share_checkpoints(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Vec<ShareCheckpoint> {
+        let count_key = DataKey::ShareCheckpointCount(token.clone(), holder.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let units = Self::share_scale(&env, &token) != BPS_DENOMINATOR;
+        let mut checkpoints = Vec::new(&env);
+        for i in 0..count {
+            let (from_period_index, share) =
+                Self::share_checkpoint(&env, &token, &holder, i, units);
+            let share_bps = Self::units_to_bps(&env, &token, share);
+            checkpoints.push_back(ShareCheckpoint { from_period_index, share_bps });
+        }
+        checkpoints
+    }
+
+    /// Like `get_share_checkpoints`, with shares in the offering's share units.
+    pub fn get_share_checkpoints_units(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Vec<ShareCheckpointUnits> {
+        let count_key = DataKey::ShareCheckpointCount(token.clone(), holder.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let units = Self::share_scale(&env, &token) != BPS_DENOMINATOR;
+        let mut checkpoints = Vec::new(&env);
+        for i in 0..count {
+            let (from_period_index, share) =
+                Self::share_checkpoint(&env, &token, &holder, i, units);
+            checkpoints.push_back(ShareCheckpointUnits { from_period_index, share });
+        }
+        checkpoints
+    }
+
+    /// (from_period_index, share in units) of the holder's checkpoint at `index`.
+    /// `units` selects `ShareCheckpointUnits` (finer precision) over bps checkpoints.
+    fn share_checkpoint(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        index: u32,
+        units: bool,
+    ) -> (u32, i128) {
+        if units {
+            let cp_key =
+                DistributionKey::ShareCheckpointUnits(token.clone(), holder.clone(), index);
+            let cp: ShareCheckpointUnits = env.storage().persistent().get(&cp_key).unwrap();
+            (cp.from_period_index, cp.share)
+        } else {
+            let cp_key = DataKey::ShareCheckpoint(token.clone(), holder.clone(), index);
+            let cp: ShareCheckpoint = env.storage().persistent().get(&cp_key).unwrap();
+            (cp.from_period_index, cp.share_bps as i128)
+        }
+    }
+
+    fn set_share_checkpoint(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        index: u32,
+        from_period_index: u32,
+        share: i128,
+    ) {
+        if Self::share_scale(env, token) != BPS_DENOMINATOR {
+            let cp_key =
+                DistributionKey::ShareCheckpointUnits(token.clone(), holder.clone(), index);
+            env.storage()
+                .persistent()
+                .set(&cp_key, &ShareCheckpointUnits { from_period_index, share });
+        } else {
+            let cp_key = DataKey::ShareCheckpoint(token.clone(), holder.clone(), index);
+            let share_bps = share as u32;
+            env.storage()
+                .persistent()
+                .set(&cp_key, &ShareCheckpoint { from_period_index, share_bps });
+        }
+    }
+
+    /// Append a checkpoint effective from the next period to be deposited.
+    /// Must run before `HolderShare` is overwritten. The first checkpoint starts
+    /// at index 0; several changes between two deposits collapse into one.
+    fn record_share_checkpoint(env: &Env, token: &Address, holder: &Address, share: i128) {
+        let count_key = DataKey::ShareCheckpointCount(token.clone(), holder.clone());
+        let mut count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        if count == 0 {
+            // Holders assigned before checkpointing keep their old share for earlier periods
+            let first = Self::stored_holder_share(env, token, holder).unwrap_or(share);
+            Self::set_share_checkpoint(env, token, holder, 0, 0, first);
+            count = 1;
+            env.storage().persistent().set(&count_key, &count);
+            if first == share {
+                return;
+            }
+        }
+
+        let from_period_index = Self::get_period_count(env.clone(), token.clone());
+        let units = Self::share_scale(env, token) != BPS_DENOMINATOR;
+        let (last_from, _) = Self::share_checkpoint(env, token, holder, count - 1, units);
+        if last_from == from_period_index {
+            Self::set_share_checkpoint(env, token, holder, count - 1, from_period_index, share);
+            return;
+        }
+
+        Self::set_share_checkpoint(env, token, holder, count, from_period_index, share);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    /// Binary-search the holder's checkpoints for the share (in share units) in effect
+    /// at `period_index`; `scale` is the offering's share scale. Holders without
+    /// checkpoints (set before checkpointing existed) use their current share.
+    fn holder_share_at(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        period_index: u32,
+        scale: i128,
+    ) -> i128 {
+        let count_key = DataKey::ShareCheckpointCount(token.clone(), holder.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if count == 0 {
+            return Self::get_holder_share_units(env.clone(), token.clone(), holder.clone());
+        }
+
+        let units = scale != BPS_DENOMINATOR;
+        let (mut lo, mut hi) = (0_u32, count);
+        let mut share = 0;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let (from_period_index, cp_share) =
+                Self::share_checkpoint(env, token, holder, mid, units);
+            if from_period_index <= period_index {
+                share = cp_share;
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        share
+    }
+
+    /// True if the holder has ever been assigned a share for this offering.
+    fn has_share_history(env: &Env, token: &Address, holder: &Address) -> bool {
+        let count_key = DataKey::ShareCheckpointCount(token.clone(), holder.clone());
+        env.storage().persistent().has(&count_key)
+            || Self::get_holder_share_units(env.clone(), token.clone(), holder.clone()) > 0
+    }
+
+    /// True once any period of the offering has been deposited against a snapshot.
+    fn is_snapshot_bound(env: &Env, token: &Address) -> bool {
+        env.storage().persistent().has(&DistributionKey::SnapshotBound(token.clone()))
+    }
+
+    /// True if some period may be paid by something other than share checkpoints
+    /// over the share scale (a snapshot or a TWAB window).
+    fn has_custom_periods(env: &Env, token: &Address) -> bool {
+        Self::is_snapshot_bound(env, token)
+            || env.storage().persistent().has(&DistributionKey::TwabBound(token.clone()))
+    }
+
+    /// Fail unless `holder` may be paid by the offering: not sanctioned, not blacklisted
+    /// and, if the offering has a whitelist, on it.
+    ///
+    /// Precedence: the platform-wide sanctions list is checked first, then the offering's
+    /// blacklist, then its whitelist. The first list that excludes the holder decides the
+    /// error, and a whitelist entry never overrides either deny list.
+    fn require_payout_eligible(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+    ) -> Result<(), RevoraError> {
+        if Self::is_sanctioned(env.clone(), holder.clone()) {
+            return Err(RevoraError::HolderSanctioned);
+        }
+        if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
+            return Err(RevoraError::HolderBlacklisted);
+        }
+        if Self::is_whitelist_enabled(env.clone(), token.clone())
+            && !Self::is_whitelisted(env.clone(), token.clone(), holder.clone())
+        {
+            return Err(RevoraError::NotWhitelisted);
+        }
+        Ok(())
+    }
+
+    /// Claim aggregated revenue across multiple unclaimed periods.
+    ///
+    /// Each period pays out at the share that was in effect when it was deposited
+    /// (see `get_holder_share_at`), or pro rata to the holder's balance for periods
+    /// deposited against a snapshot; periods before the holder's entitlement start are skipped.
+    /// Capped at 50 periods per transaction.
+    ///
+    /// ### Parameters
+    /// - `holder`: The address of the token holder. Must provide authentication.
+    /// - `token`: The token representing the offering.
+    /// - `max_periods`: Maximum number of periods to process (0 = 50). Ignored in
+    ///   accumulator mode, where a claim settles everything at constant cost.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The total payout amount on success.
+    /// - `Err(RevoraError::HolderSanctioned)`, `HolderBlacklisted` or `NotWhitelisted` if
+    ///   the holder is sanctioned, blacklisted or not whitelisted.
+    /// - `Err(RevoraError::NoPendingClaims)` if no share is set or all periods are claimed.
+    /// - `Err(RevoraError::ClaimDelayNotElapsed)` if no period in range has passed its claim delay.
+    pub fn claim(
+        env: Env,
+        holder: Address,
+        token: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth();
+
+        let (payout, payment_token) = Self::settle_claim(&env, &holder, &token, max_periods)?;
+        // Transfer only if there is a positive payout
+        if payout > 0 {
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &holder,
+                &payout,
+            );
+        }
+        Ok(payout)
+    }
+
+    /// Claim specific periods, in any order; later claims skip them. Each period pays
+    /// as through `claim`, and one still releasing can be listed again later.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The total payout amount on success.
+    /// - `Err(RevoraError::HolderSanctioned)`, `HolderBlacklisted` or `NotWhitelisted` if
+    ///   the holder is sanctioned, blacklisted or not whitelisted.
+    /// - `Err(RevoraError::NoPendingClaims)` if no share is set or `period_ids` is empty.
+    /// - `Err(RevoraError::LimitReached)` if more than 50 periods are listed.
+    /// - `Err(RevoraError::InvalidPeriodId)` if a period was never deposited.
+    /// - `Err(RevoraError::PeriodNotClaimable)` if a period is listed twice, already claimed,
+    ///   before the holder's entitlement start, or a Merkle period.
+    /// - `Err(RevoraError::ClaimDeadlinePassed)` if a period is past its claim deadline.
+    /// - `Err(RevoraError::ClaimDelayNotElapsed)` if a period has nothing released yet.
+    /// - `Err(RevoraError::DistributionModeConflict)` if the offering uses accumulator mode.
+    pub fn claim_periods(
+        env: Env,
+        holder: Address,
+        token: Address,
+        period_ids: Vec<u64>,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth();
+
+        let (payout, payment_token) =
+            Self::settle_period_claims(&env, &holder, &token, &period_ids)?;
+        if payout > 0 {
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &holder,
+                &payout,
+            );
+        }
+        Ok(payout)
+    }
+
+    /// Claim on several offerings in one call, with one transfer per payment token.
+    ///
+    /// Each `(token, max_periods)` entry is settled like `claim`. An entry that fails
+    /// (e.g. `HolderBlacklisted`, `NoPendingClaims`) is reported in its result and
+    /// leaves that offering untouched; the other entries still go through.
+    ///
+    /// ### Returns
+    /// - `Ok(results)` with one `ClaimResult` per entry, in input order. `error` holds
+    ///   the `RevoraError` code for failed entries.
+    /// - `Err(RevoraError::LimitReached)` if more than 10 entries are listed.
+    pub fn claim_many(
+        env: Env,
+        holder: Address,
+        claims: Vec<(Address, u32)>,
+    ) -> Result<Vec<ClaimResult>, RevoraError> {
+        holder.require_auth();
+        if claims.len() > MAX_CLAIM_MANY_ENTRIES {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let mut results = Vec::new(&env);
+        let mut totals: Map<Address, i128> = Map::new(&env);
+        for (token, max_periods) in claims.iter() {
+            match Self::settle_claim(&env, &holder, &token, max_periods) {
+                Ok((amount, payment_token)) => {
+                    let total = totals.get(payment_token.clone()).unwrap_or(0);
+                    totals.set(payment_token, total + amount);
+                    results.push_back(ClaimResult { token, amount, error: None });
+                }
+                Err(err) => {
+                    results.push_back(ClaimResult { token, amount: 0, error: Some(err as u32) });
+                }
+            }
+        }
+
+        let contract_addr = env.current_contract_address();
+        for (payment_token, total) in totals.iter() {
+            if total > 0 {
+                token::Client::new(&env, &payment_token).transfer(&contract_addr, &holder, &total);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Validate and book a holder's claim on one offering: advances claim progress,
+    /// updates liabilities and emits the claim event. Returns the payout and its
+    /// payment token; the caller makes the transfer. Fails before mutating state.
+    fn settle_claim(
+        env: &Env,
+        holder: &Address,
+        token: &Address,
+        max_periods: u32,
+    ) -> Result<(i128, Address), RevoraError> {
+        Self::require_payout_eligible(env, token, holder)?;
+
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            let (payout, payment_token) = Self::book_accumulated_claim(env, holder, token)?;
+            Self::record_payout(env, token, &payment_token, payout);
+            env.events().publish((EVENT_CLAIM_ACC, holder.clone(), token.clone()), payout);
+            return Ok((payout, payment_token));
+        }
+
+        let (total_payout, payment_token, booked) =
+            Self::book_pending_periods(env, holder, token, max_periods)?;
+        let mut claimed_periods = Vec::new(env);
+        for (period_id, _) in booked.iter() {
+            claimed_periods.push_back(period_id);
+        }
+        if total_payout > 0 {
+            Self::record_payout(env, token, &payment_token, total_payout);
+        }
+        env.events()
+            .publish((EVENT_CLAIM, holder.clone(), token.clone()), (total_payout, claimed_periods));
+
+        Ok((total_payout, payment_token))
+    }
+
+    /// Book everything `holder` can claim from up to `max_periods` periods and advance
+    /// their claim index. Returns the total, the payment token and the periods paid
+    /// from with what each paid; the caller settles the liability and emits the event.
+    fn book_pending_periods(
+        env: &Env,
+        holder: &Address,
+        token: &Address,
+        max_periods: u32,
+    ) -> Result<(i128, Address, BookedPeriods), RevoraError> {
+        if !Self::is_snapshot_bound(env, token) && !Self::has_share_history(env, token, holder) {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        let custom_periods = Self::has_custom_periods(env, token);
+
+        let count_key = DataKey::PeriodCount(token.clone());
+        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
+        let start_idx = Self::next_claim_index(env, token, holder);
+
+        if start_idx >= period_count {
+            return Err(RevoraError::NoPendingClaims);
+        }
+
+        let effective_max = if max_periods == 0 || max_periods > MAX_CLAIM_PERIODS {
+            MAX_CLAIM_PERIODS
+        } else {
+            max_periods
+        };
+        let end_idx = core::cmp::min(start_idx + effective_max, period_count);
+
+        let now = env.ledger().timestamp();
+
+        let ctx = Self::payout_context(env, token, custom_periods, now);
+        let mut total_payout: i128 = 0;
+        let mut claimed_periods = Vec::new(env);
+        let mut period_payouts: Vec<i128> = Vec::new(env);
+        let mut period_settled: Vec<Option<i128>> = Vec::new(env);
+        let mut last_claimed_idx = start_idx;
+        // The claim index stops at the first period that is still delayed or releasing;
+        // fully released periods after it are marked in the claimed-period bitmap
+        let mut releasing = false;
+        let mut marked: Vec<u32> = Vec::new(env);
+        let mut bits: u128 = 0;
+
+        for i in start_idx..end_idx {
+            if i == start_idx || i % 128 == 0 {
+                bits = Self::claimed_bits(env, token, holder, i / 128);
+            }
+            if bits & (1 << (i % 128)) != 0 {
+                if !releasing {
+                    last_claimed_idx = i + 1;
+                }
+                continue;
+            }
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+            if Self::is_claim_delayed(env, token, period_id, deposit_time, &ctx) {
+                releasing = true;
+                continue;
+            }
+            // Merkle periods are paid out through `claim_merkle` only; expired ones are swept
+            if !Self::is_merkle_period(env, token, period_id)
+                && !Self::is_period_expired(env, token, period_id)
+            {
+                let (payout, settled_share) = Self::holder_period_payout(
+                    env,
+                    token,
+                    holder,
+                    i,
+                    period_id,
+                    deposit_time,
+                    &ctx,
+                )?;
+                let released = settled_share.is_some();
+                total_payout =
+                    total_payout.checked_add(payout).ok_or(RevoraError::ArithmeticOverflow)?;
+                claimed_periods.push_back(period_id);
+                period_payouts.push_back(payout);
+                period_settled.push_back(settled_share);
+                if releasing && released {
+                    marked.push_back(i);
+                }
+                releasing = releasing || !released;
+            }
+            if !releasing {
+                last_claimed_idx = i + 1;
+            }
+        }
+
+        if last_claimed_idx == start_idx && total_payout == 0 {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+
+        let mut booked = Vec::new(env);
+        for (i, period_id) in claimed_periods.iter().enumerate() {
+            let payout = period_payouts.get(i as u32).unwrap();
+            let settled_share = period_settled.get(i as u32).unwrap();
+            Self::book_period_claim(env, token, holder, period_id, payout, settled_share, &ctx);
+            booked.push_back((period_id, payout));
+        }
+        Self::mark_periods_claimed(env, token, holder, &marked);
+        let last_claimed_idx = Self::advance_claim_index(
+            env,
+            token,
+            holder,
+            start_idx,
+            last_claimed_idx,
+            period_count,
+        );
+
+        // Advance claim index only for periods actually claimed (respecting delay)
+        env.storage().persistent().set(&idx_key, &last_claimed_idx);
+
+        let pt_key = DataKey::PaymentToken(token.clone());
+        let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+        Ok((total_payout, payment_token, booked))
+    }
+
+    /// `settle_claim` for an explicit list of periods (`claim_periods`).
+    fn settle_period_claims(
+        env: &Env,
+        holder: &Address,
+        token: &Address,
+        period_ids: &Vec<u64>,
+    ) -> Result<(i128, Address), RevoraError> {
+        Self::require_payout_eligible(env, token, holder)?;
+        // The accumulator does not track periods individually
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+        if !Self::is_snapshot_bound(env, token) && !Self::has_share_history(env, token, holder) {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        if period_ids.is_empty() {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        if period_ids.len() > MAX_CLAIM_PERIODS {
+            return Err(RevoraError::LimitReached);
+        }
+        // Stop at the first id that was never deposited, so the scan below is not
+        // run to the last period looking for it
+        for period_id in period_ids.iter() {
+            if !env.storage().persistent().has(&DataKey::PeriodRevenue(token.clone(), period_id)) {
+                return Err(RevoraError::InvalidPeriodId);
+            }
+        }
+
+        // Find the listed periods among those from the claim index on
+        let start_idx = Self::next_claim_index(env, token, holder);
+        let period_count = Self::get_period_count(env.clone(), token.clone());
+        let mut indices: Map<u64, u32> = Map::new(env);
+        let mut i = start_idx;
+        while indices.len() < period_ids.len() && i < period_count {
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            if period_ids.contains(period_id) {
+                indices.set(period_id, i);
+            }
+            i += 1;
+        }
+
+        let now = env.ledger().timestamp();
+        let ctx = Self::payout_context(env, token, Self::has_custom_periods(env, token), now);
+
+        let mut total_payout: i128 = 0;
+        let mut claimed_periods: Vec<u64> = Vec::new(env);
+        let mut period_payouts: Vec<i128> = Vec::new(env);
+        let mut period_settled: Vec<Option<i128>> = Vec::new(env);
+        let mut marked: Vec<u32> = Vec::new(env);
+        for period_id in period_ids.iter() {
+            let index = match indices.get(period_id) {
+                Some(index) => index,
+                // Before the claim index
+                None => return Err(RevoraError::PeriodNotClaimable),
+            };
+            let bits = Self::claimed_bits(env, token, holder, index / 128);
+            if bits & (1 << (index % 128)) != 0
+                || claimed_periods.contains(period_id)
+                || Self::is_merkle_period(env, token, period_id)
+            {
+                return Err(RevoraError::PeriodNotClaimable);
+            }
+            if Self::is_period_expired(env, token, period_id) {
+                return Err(RevoraError::ClaimDeadlinePassed);
+            }
+            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+            if Self::is_claim_delayed(env, token, period_id, deposit_time, &ctx) {
+                return Err(RevoraError::ClaimDelayNotElapsed);
+            }
+            let (payout, settled_share) = Self::holder_period_payout(
+                env,
+                token,
+                holder,
+                index,
+                period_id,
+                deposit_time,
+                &ctx,
+            )?;
+            let released = settled_share.is_some();
+            if payout == 0 && !released {
+                return Err(RevoraError::ClaimDelayNotElapsed);
+            }
+            total_payout =
+                total_payout.checked_add(payout).ok_or(RevoraError::ArithmeticOverflow)?;
+            claimed_periods.push_back(period_id);
+            period_payouts.push_back(payout);
+            period_settled.push_back(settled_share);
+            if released {
+                marked.push_back(index);
+            }
+        }
+
+        for (i, period_id) in claimed_periods.iter().enumerate() {
+            let payout = period_payouts.get(i as u32).unwrap();
+            let settled_share = period_settled.get(i as u32).unwrap();
+            Self::book_period_claim(env, token, holder, period_id, payout, settled_share, &ctx);
+        }
+        Self::mark_periods_claimed(env, token, holder, &marked);
+        let next_idx =
+            Self::advance_claim_index(env, token, holder, start_idx, start_idx, period_count);
+        if next_idx > start_idx {
+            let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
+            env.storage().persistent().set(&idx_key, &next_idx);
+        }
+
+        let pt_key = DataKey::PaymentToken(token.clone());
+        let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+        if total_payout > 0 {
+            Self::record_payout(env, token, &payment_token, total_payout);
+        }
+        env.events()
+            .publish((EVENT_CLAIM, holder.clone(), token.clone()), (total_payout, claimed_periods));
+
+        Ok((total_payout, payment_token))
+    }
+
+    /// Add a holder's payout from one period to the period's claimed total and, under
+    /// a release schedule, to their partial claim while the period is still releasing.
+    /// Once the period is fully released (`settled_share` is the holder's share of it),
+    /// the share and everything the holder took from the period go to its paid total.
+    fn book_period_claim(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        period_id: u64,
+        payout: i128,
+        settled_share: Option<i128>,
+        ctx: &PayoutContext,
+    ) {
+        let claimed_key = AccountingKey::PeriodClaimed(token.clone(), period_id);
+        Self::add_to_counter(env, &claimed_key, payout);
+        let mut paid = payout;
+        if ctx.releases {
+            let partial_key =
+                DistributionKey::PartialClaim(token.clone(), holder.clone(), period_id);
+            let partial: i128 = env.storage().persistent().get(&partial_key).unwrap_or(0);
+            if settled_share.is_some() {
+                env.storage().persistent().remove(&partial_key);
+                paid += partial;
+            } else if payout > 0 {
+                env.storage().persistent().set(&partial_key, &(partial + payout));
+            }
+        }
+        if let Some(share) = settled_share {
+            let paid_key = AccountingKey::PeriodPaidShare(token.clone(), period_id);
+            let (paid_share, paid_total): (i128, i128) =
+                env.storage().persistent().get(&paid_key).unwrap_or((0, 0));
+            env.storage()
+                .persistent()
+                .set(&paid_key, &(paid_share.saturating_add(share), paid_total + paid));
+        }
+    }
+
+    /// Word `word` of a holder's claimed-period bitmap.
+    fn claimed_bits(env: &Env, token: &Address, holder: &Address, word: u32) -> u128 {
+        let key = DistributionKey::ClaimedPeriods(token.clone(), holder.clone(), word);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    fn mark_periods_claimed(env: &Env, token: &Address, holder: &Address, indices: &Vec<u32>) {
+        for index in indices.iter() {
+            let bits = Self::claimed_bits(env, token, holder, index / 128);
+            let key = DistributionKey::ClaimedPeriods(token.clone(), holder.clone(), index / 128);
+            env.storage().persistent().set(&key, &(bits | (1 << (index % 128))));
+        }
+    }
+
+    /// Move a claim index at `index` past the claimed periods right after it, and drop
+    /// the bitmap words between `start_idx` and the new index, which no longer matter.
+    fn advance_claim_index(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        start_idx: u32,
+        mut index: u32,
+        period_count: u32,
+    ) -> u32 {
+        while index < period_count {
+            let bits = Self::claimed_bits(env, token, holder, index / 128);
+            let offset = index % 128;
+            let run = (bits >> offset).trailing_ones();
+            index += run;
+            // Only a run to the end of the word can go on in the next one
+            if offset + run < 128 {
+                break;
+            }
+        }
+        for word in start_idx / 128..index / 128 {
+            let key = DistributionKey::ClaimedPeriods(token.clone(), holder.clone(), word);
+            env.storage().persistent().remove(&key);
+        }
+        index
+    }
+
+    /// Return unclaimed, unexpired period IDs for a holder on an offering, from their
+    /// entitlement start. Periods claimed through `claim_periods` are left out.
+    /// Ordering: by deposit index (creation order), deterministic (#38).
+    pub fn get_pending_periods(env: Env, token: Address, holder: Address) -> Vec<u64> {
+        let mut periods = Vec::new(&env);
+        for (_, period_id) in Self::pending_period_entries(&env, &token, &holder).iter() {
+            periods.push_back(period_id);
+        }
+        periods
+    }
+
+    /// Same periods as `get_pending_periods`, each with the earliest time `claim` pays
+    /// the holder something from it: once the claim delay has passed and, under the
+    /// period's release schedule, the cliff and enough of the linear window for the
+    /// next unit of their entitlement to be released.
+    pub fn get_pending_periods_detailed(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Vec<PendingPeriod> {
+        let now = env.ledger().timestamp();
+        let ctx = Self::payout_context(&env, &token, Self::has_custom_periods(&env, &token), now);
+
+        let mut periods = Vec::new(&env);
+        for (index, period_id) in Self::pending_period_entries(&env, &token, &holder).iter() {
+            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+            let delay_secs = Self::period_claim_delay(&env, &token, period_id, &ctx);
+            let release_secs =
+                Self::period_release_wait(&env, &token, &holder, index, period_id, &ctx);
+            let claimable_at =
+                deposit_time.saturating_add(core::cmp::max(delay_secs, release_secs));
+            periods.push_back(PendingPeriod { period_id, claimable_at });
+        }
+        periods
+    }
+
+    /// (index, period_id) of a holder's unclaimed, unexpired, non-Merkle periods from
+    /// their claim index on, in deposit order.
+    fn pending_period_entries(env: &Env, token: &Address, holder: &Address) -> Vec<(u32, u64)> {
+        let count_key = DataKey::PeriodCount(token.clone());
+        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let start_idx = Self::next_claim_index(env, token, holder);
+        let mut entries = Vec::new(env);
+        let mut bits: u128 = 0;
+        for i in start_idx..period_count {
+            if i == start_idx || i % 128 == 0 {
+                bits = Self::claimed_bits(env, token, holder, i / 128);
+            }
+            if bits & (1 << (i % 128)) != 0 {
+                continue;
+            }
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            if !Self::is_merkle_period(env, token, period_id)
+                && !Self::is_period_expired(env, token, period_id)
+            {
+                entries.push_back((i, period_id));
+            }
+        }
+        entries
+    }
+
+    /// Seconds after deposit until the period's release schedule lets the holder take
+    /// another unit: the cliff, plus the part of the linear window after which
+    /// `entitled * vesting / linear_secs` exceeds what they already took. The whole
+    /// window if nothing more will be released.
+    fn period_release_wait(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        period_index: u32,
+        period_id: u64,
+        ctx: &PayoutContext,
+    ) -> u64 {
+        let release = Self::get_period_release(env.clone(), token.clone(), period_id);
+        if release.linear_secs == 0 {
+            return release.cliff_secs;
+        }
+        let linear = release.linear_secs as i128;
+        let partial_key = DistributionKey::PartialClaim(token.clone(), holder.clone(), period_id);
+        let taken: i128 = env.storage().persistent().get(&partial_key).unwrap_or(0);
+        let vesting = Self::holder_period_share(env, token, holder, period_index, period_id, ctx)
+            .and_then(|(share, scale)| {
+                Self::period_payout(env, token, period_id, share, scale, ctx, taken)
+            })
+            .and_then(|entitled| {
+                if entitled <= taken {
+                    return Ok(linear);
+                }
+                let next = taken.saturating_add(1);
+                Self::mul_div_rounded(next, linear, entitled, RoundingMode::Ceiling)
+            })
+            .unwrap_or(linear);
+        release.cliff_secs.saturating_add(core::cmp::min(vesting, linear) as u64)
+    }
+
+    /// Preview the total claimable amount for a holder without mutating state.
+    ///
+    /// This method respects each period's claim delay and only sums periods that have passed it.
+    /// Under a release schedule only the released, not yet claimed part of each period counts.
+    /// Periods past their claim deadline are not counted.
+    ///
+    /// ### Parameters
+    /// - `token`: The token representing the offering.
+    /// - `holder`: The address of the token holder.
+    ///
+    /// ### Returns
+    /// The total amount (i128) currently claimable by the holder; 0 if they are
+    /// blacklisted or missing from the offering's whitelist, or if the total cannot be
+    /// computed (see `get_claimable_checked`).
+    pub fn get_claimable(env: Env, token: Address, holder: Address) -> i128 {
+        Self::get_claimable_checked(env, token, holder).unwrap_or(0)
+    }
+
+    /// Like `get_claimable`, but reports a total that cannot be computed instead of
+    /// returning 0.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` the total currently claimable by the holder.
+    /// - `Err(RevoraError::ArithmeticOverflow)` if the total does not fit in i128, in
+    ///   which case `claim` fails the same way.
+    pub fn get_claimable_checked(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Result<i128, RevoraError> {
+        if Self::require_payout_eligible(&env, &token, &holder).is_err() {
+            return Ok(0);
+        }
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Self::accumulated_claimable(&env, &token, &holder);
+        }
+        if !Self::is_snapshot_bound(&env, &token) && !Self::has_share_history(&env, &token, &holder)
+        {
+            return Ok(0);
+        }
+        let custom_periods = Self::has_custom_periods(&env, &token);
+
+        let count_key = DataKey::PeriodCount(token.clone());
+        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let start_idx = Self::next_claim_index(&env, &token, &holder);
+
+        let now = env.ledger().timestamp();
+        let ctx = Self::payout_context(&env, &token, custom_periods, now);
+
+        let mut total: i128 = 0;
+        let mut bits: u128 = 0;
+        for i in start_idx..period_count {
+            if i == start_idx || i % 128 == 0 {
+                bits = Self::claimed_bits(&env, &token, &holder, i / 128);
+            }
+            if bits & (1 << (i % 128)) != 0 {
+                continue;
+            }
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+            if Self::is_claim_delayed(&env, &token, period_id, deposit_time, &ctx)
+                || Self::is_merkle_period(&env, &token, period_id)
+                || Self::is_period_expired(&env, &token, period_id)
+            {
+                continue;
+            }
+            let (payout, _) = Self::holder_period_payout(
+                &env,
+                &token,
+                &holder,
+                i,
+                period_id,
+                deposit_time,
+                &ctx,
+            )?;
+            total = total.checked_add(payout).ok_or(RevoraError::ArithmeticOverflow)?;
+        }
+        Ok(total)
+    }
+
+    fn payout_context(env: &Env, token: &Address, custom_periods: bool, now: u64) -> PayoutContext {
+        PayoutContext {
+            custom_periods,
+            mode: Self::offering_rounding_mode(env, token),
+            releases: env
+                .storage()
+                .persistent()
+                .has(&DistributionKey::ReleaseSchedule(token.clone())),
+            redistributions: env
+                .storage()
+                .persistent()
+                .has(&DistributionKey::HasRedistributions(token.clone())),
+            delays: env.storage().persistent().has(&DistributionKey::HasClaimDelays(token.clone())),
+            now,
+        }
+    }
+
+    /// Claim delay fixed for a period at its deposit (0 if none).
+    fn period_claim_delay(env: &Env, token: &Address, period_id: u64, ctx: &PayoutContext) -> u64 {
+        if !ctx.delays {
+            return 0;
+        }
+        let key = DistributionKey::PeriodClaimDelay(token.clone(), period_id);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// True while the claim delay of a period has not elapsed since its deposit.
+    fn is_claim_delayed(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        deposit_time: u64,
+        ctx: &PayoutContext,
+    ) -> bool {
+        let delay_secs = Self::period_claim_delay(env, token, period_id, ctx);
+        delay_secs > 0 && ctx.now < deposit_time.saturating_add(delay_secs)
+    }
+
+    /// What a holder can take from the period at `period_index` now, and their share of
+    /// the period once it is fully released (`None` while it is still releasing). Under
+    /// the period's release schedule this is the released part of their entitlement
+    /// less what they already took from the period.
+    fn holder_period_payout(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        period_index: u32,
+        period_id: u64,
+        deposit_time: u64,
+        ctx: &PayoutContext,
+    ) -> Result<(i128, Option<i128>), RevoraError> {
+        let (share, scale) =
+            Self::holder_period_share(env, token, holder, period_index, period_id, ctx)?;
+        let release: Option<ReleaseSchedule> = if ctx.releases {
+            env.storage()
+                .persistent()
+                .get(&DistributionKey::PeriodRelease(token.clone(), period_id))
+        } else {
+            None
+        };
+        let release = match release {
+            Some(release) => release,
+            None => {
+                let payout = Self::period_payout(env, token, period_id, share, scale, ctx, 0)?;
+                return Ok((payout, Some(share)));
+            }
+        };
+        let partial_key = DistributionKey::PartialClaim(token.clone(), holder.clone(), period_id);
+        let taken: i128 = env.storage().persistent().get(&partial_key).unwrap_or(0);
+        let entitled = Self::period_payout(env, token, period_id, share, scale, ctx, taken)?;
+
+        let elapsed = ctx.now.saturating_sub(deposit_time);
+        if elapsed < release.cliff_secs {
+            return Ok((0, None));
+        }
+        let vesting = elapsed - release.cliff_secs;
+        if vesting >= release.linear_secs {
+            return Ok((core::cmp::max(entitled - taken, 0), Some(share)));
+        }
+        let released = Self::mul_div_rounded(
+            entitled,
+            vesting as i128,
+            release.linear_secs as i128,
+            RoundingMode::Truncation,
+        )?;
+        Ok((core::cmp::max(released - taken, 0), None))
+    }
+
+    /// A holder's fraction of the period at `period_index` as (numerator, denominator):
+    /// their balance over the supply in the offering token's snapshot, scaled by the
+    /// offering's `revenue_share_bps`, for snapshot-bound periods, their share-seconds
+    /// over the window for TWAB periods, and their checkpoint over the share scale
+    /// otherwise. Negative balances count as zero.
+    fn holder_period_share(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        period_index: u32,
+        period_id: u64,
+        ctx: &PayoutContext,
+    ) -> Result<(i128, i128), RevoraError> {
+        let scale = Self::share_scale(env, token);
+        if ctx.custom_periods {
+            if let Some(ledger) = Self::get_period_snapshot(env.clone(), token.clone(), period_id) {
+                let snapshot = SnapshotTokenClient::new(env, token);
+                let supply = snapshot.total_supply_at(&ledger);
+                if supply <= 0 {
+                    return Ok((0, 1));
+                }
+                let balance = snapshot.balance_at(holder, &ledger).clamp(0, supply);
+                return Self::snapshot_share(env, token, balance, supply);
+            }
+            if let Some((start, end)) =
+                Self::get_period_twab_window(env.clone(), token.clone(), period_id)
+            {
+                let (share_seconds, duration) =
+                    Self::time_weighted_share(env, token, holder, start, end)?;
+                let denominator =
+                    scale.checked_mul(duration).ok_or(RevoraError::ArithmeticOverflow)?;
+                return Ok((share_seconds, denominator));
+            }
+        }
+        Ok((Self::holder_share_at(env, token, holder, period_index, scale), scale))
+    }
+
+    /// A snapshot balance as (numerator, denominator) of the period revenue:
+    /// `balance * revenue_share_bps` over `supply * 10000`, so the whole supply
+    /// shares the offering's revenue share and no more.
+    fn snapshot_share(
+        env: &Env,
+        token: &Address,
+        balance: i128,
+        supply: i128,
+    ) -> Result<(i128, i128), RevoraError> {
+        let bps = Self::offering_share_bps(env, token) as i128;
+        let numerator = balance.checked_mul(bps).ok_or(RevoraError::ArithmeticOverflow)?;
+        let denominator = supply.checked_mul(10_000).ok_or(RevoraError::ArithmeticOverflow)?;
+        Ok((numerator, denominator))
+    }
+
+    /// One holder's payout for one period: `revenue * share / scale` in the offering's
+    /// rounding mode (truncated if `fix_period_rounding` said so), plus their part of
+    /// any revenue redistributed into the period, capped at what is left of the
+    /// period's deposit as a last guard against over-distribution.
+    /// Settled dust is no longer part of what is left; `taken`, the holder's own
+    /// partial claims on the period, still is.
+    fn period_payout(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        share: i128,
+        scale: i128,
+        ctx: &PayoutContext,
+        taken: i128,
+    ) -> Result<i128, RevoraError> {
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let mut revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
+        let truncated_key = DistributionKey::PeriodTruncated(token.clone(), period_id);
+        let mode = if Self::rounds_up(ctx.mode) && env.storage().persistent().has(&truncated_key) {
+            RoundingMode::Truncation
+        } else {
+            ctx.mode
+        };
+        let mut payout = Self::mul_div_rounded(revenue, share, scale, mode)?;
+        if ctx.redistributions {
+            for (amount, split_over) in Self::get_redistributed(env, token, period_id).iter() {
+                let extra =
+                    Self::mul_div_rounded(amount, share, split_over, RoundingMode::Truncation)?;
+                payout = payout.checked_add(extra).ok_or(RevoraError::ArithmeticOverflow)?;
+                revenue += amount;
+            }
+        }
+        let claimed_key = AccountingKey::PeriodClaimed(token.clone(), period_id);
+        let claimed: i128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        let dust = Self::get_period_dust(env.clone(), token.clone(), period_id).unwrap_or(0);
+        Ok(core::cmp::min(payout, revenue - claimed - dust + taken))
+    }
+
+    /// Revenue redistributed into a period, as (amount, summed share it is split over).
+    fn get_redistributed(env: &Env, token: &Address, period_id: u64) -> Vec<(i128, i128)> {
+        let key = DistributionKey::Redistributed(token.clone(), period_id);
+        env.storage().persistent().get(&key).unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// `amount * numerator / denominator` in the given rounding mode, with a 256-bit
+    /// intermediate product so only a result outside i128 can fail. All share and
+    /// payout math goes through here.
+    ///
+    /// ### Returns
+    /// - `Err(RevoraError::ArithmeticOverflow)` if the result does not fit in i128 or
+    ///   `denominator` is 0.
+    fn mul_div_rounded(
+        amount: i128,
+        numerator: i128,
+        denominator: i128,
+        mode: RoundingMode,
+    ) -> Result<i128, RevoraError> {
+        let negative = (amount < 0) ^ (numerator < 0) ^ (denominator < 0);
+        let denominator_abs = denominator.unsigned_abs();
+        let (quotient, remainder) =
+            Self::mul_div_wide(amount.unsigned_abs(), numerator.unsigned_abs(), denominator_abs)
+                .ok_or(RevoraError::ArithmeticOverflow)?;
+
+        // Compare the fraction with one half without overflow: r vs denominator - r
+        let complement = denominator_abs - remainder;
+        let round_away_from_zero = remainder != 0
+            && match mode {
+                RoundingMode::Truncation | RoundingMode::LargestRemainder => false,
+                RoundingMode::RoundHalfUp => remainder >= complement,
+                RoundingMode::RoundHalfEven => {
+                    remainder > complement || (remainder == complement && quotient % 2 != 0)
+                }
+                RoundingMode::Ceiling => !negative,
+            };
+        let magnitude = if round_away_from_zero {
+            quotient.checked_add(1).ok_or(RevoraError::ArithmeticOverflow)?
+        } else {
+            quotient
+        };
+
+        if negative {
+            // i128::MIN has magnitude 2^127
+            if magnitude > i128::MIN.unsigned_abs() {
+                return Err(RevoraError::ArithmeticOverflow);
+            }
+            Ok((magnitude as i128).wrapping_neg())
+        } else {
+            i128::try_from(magnitude).map_err(|_| RevoraError::ArithmeticOverflow)
+        }
+    }
+
+    /// Unsigned `a * b / d` over a 256-bit product, returning quotient and remainder.
+    /// `None` if `d` is 0 or the quotient does not fit in 128 bits.
+    fn mul_div_wide(a: u128, b: u128, d: u128) -> Option<(u128, u128)> {
+        if d == 0 {
+            return None;
+        }
+        // 128x128 -> 256-bit schoolbook product on 64-bit limbs
+        const LOW: u128 = u64::MAX as u128;
+        let (a_hi, a_lo) = (a >> 64, a & LOW);
+        let (b_hi, b_lo) = (b >> 64, b & LOW);
+        let lo_lo = a_lo * b_lo;
+        let hi_lo = a_hi * b_lo;
+        let lo_hi = a_lo * b_hi;
+        let hi_hi = a_hi * b_hi;
+        let middle = (lo_lo >> 64) + (hi_lo & LOW) + (lo_hi & LOW);
+        let lo = (lo_lo & LOW) | (middle << 64);
+        let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+
+        if hi == 0 {
+            return Some((lo / d, lo % d));
+        }
+        if hi >= d {
+            return None;
+        }
+        // Restoring long division of (hi, lo) by d; hi < d keeps the quotient in 128 bits
+        let mut remainder = hi;
+        let mut quotient: u128 = 0;
+        for i in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((lo >> i) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= d {
+                remainder = remainder.wrapping_sub(d);
+                quotient |= 1;
+            }
+        }
+        Some((quotient, remainder))
+    }
+
+    /// Rounding mode configured by the offering's current issuer.
+    fn offering_rounding_mode(env: &Env, token: &Address) -> RoundingMode {
+        match Self::get_current_issuer(env, token) {
+            Some(issuer) => Self::get_rounding_mode(env.clone(), issuer, token.clone()),
+            None => RoundingMode::Truncation,
+        }
+    }
+
+    /// True for rounding modes that can pay a holder more than their exact share.
+    fn rounds_up(mode: RoundingMode) -> bool {
+        matches!(
+            mode,
+            RoundingMode::RoundHalfUp | RoundingMode::RoundHalfEven | RoundingMode::Ceiling
+        )
+    }
+
+    /// Decide at deposit whether a period can be paid in the offering's rounding mode,
+    /// so every holder of the period is rounded alike whatever order they claim in.
+    /// Rounding up adds less than one unit per holder; the mode is kept only while the
+    /// unallocated part of `revenue` covers one unit per registered holder, and TWAB
+    /// periods, whose shares change within the window, are always truncated.
+    fn fix_period_rounding(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        revenue: i128,
+    ) -> Result<(), RevoraError> {
+        if !Self::rounds_up(Self::offering_rounding_mode(env, token)) {
+            return Ok(());
+        }
+        let total_share = Self::total_allocated_units(env, token);
+        let allocated = Self::mul_div_rounded(
+            revenue,
+            total_share,
+            Self::share_scale(env, token),
+            RoundingMode::Truncation,
+        )?;
+        let holders = Self::get_holder_count(env.clone(), token.clone()) as i128;
+        if Self::get_twab_config(env.clone(), token.clone()) || allocated + holders > revenue {
+            env.storage()
+                .persistent()
+                .set(&DistributionKey::PeriodTruncated(token.clone(), period_id), &true);
+        }
+        Ok(())
+    }
+
+    // ── Push distribution ─────────────────────────────────────
+
+    /// Set or clear the keeper allowed to call `push_distribution` for an offering.
+    pub fn set_keeper(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        keeper: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = DistributionKey::Keeper(token.clone());
+        match &keeper {
+            Some(addr) => env.storage().persistent().set(&key, addr),
+            None => env.storage().persistent().remove(&key),
+        }
+        env.events().publish((EVENT_KEEPER_SET, issuer, token), keeper);
+        Ok(())
+    }
+
+    /// Return the keeper for an offering, if one is set.
+    pub fn get_keeper(env: Env, token: Address) -> Option<Address> {
+        env.storage().persistent().get(&DistributionKey::Keeper(token))
+    }
+
+    /// Pay out pending revenue to a page of registered holders, on their behalf.
+    ///
+    /// For each holder in `[start, start + limit)` of the registry this does what
+    /// `claim(holder, token, max_periods)` would, or with `period_id` what
+    /// `claim_periods(holder, token, [period_id])` would, and transfers the payout to
+    /// the holder. Sanctioned and blacklisted holders and holders off the offering's
+    /// whitelist are skipped and listed in the result; holders with nothing claimable
+    /// are skipped silently. Limit capped at MAX_PAGE_LIMIT (20).
+    ///
+    /// ### Returns
+    /// - `Ok(PushDistributionResult)` with the next cursor.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the keeper.
+    /// - `Err(RevoraError::InvalidPeriodId)` if nothing was deposited for `period_id`.
+    /// - `Err(RevoraError::DistributionModeConflict)` for a Merkle `period_id` or one in
+    ///   accumulator mode.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn push_distribution(
+        env: Env,
+        caller: Address,
+        token: Address,
+        period_id: Option<u64>,
+        max_periods: u32,
+        start: u32,
+        limit: u32,
+    ) -> Result<PushDistributionResult, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_issuer_or_keeper(&env, &caller, &token)?;
+        caller.require_auth();
+        let period_ids = match period_id {
+            Some(period_id) => {
+                let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+                if !env.storage().persistent().has(&rev_key) {
+                    return Err(RevoraError::InvalidPeriodId);
+                }
+                if Self::is_accumulator_mode(env.clone(), token.clone())
+                    || Self::is_merkle_period(&env, &token, period_id)
+                {
+                    return Err(RevoraError::DistributionModeConflict);
+                }
+                Some(Vec::from_array(&env, [period_id]))
+            }
+            None => None,
+        };
+
+        let (holders, next_cursor) =
+            Self::get_holders_page(env.clone(), token.clone(), start, limit);
+        let mut paid = Vec::new(&env);
+        let mut skipped_blacklisted = Vec::new(&env);
+        let mut skipped_not_whitelisted = Vec::new(&env);
+        let mut skipped_sanctioned = Vec::new(&env);
+        let mut failed = Vec::new(&env);
+        let contract_addr = env.current_contract_address();
+        for holder in holders.iter() {
+            let settled = match &period_ids {
+                Some(period_ids) => Self::settle_period_claims(&env, &holder, &token, period_ids),
+                None => Self::settle_claim(&env, &holder, &token, max_periods),
+            };
+            match settled {
+                Ok((amount, payment_token)) => {
+                    if amount > 0 {
+                        token::Client::new(&env, &payment_token).transfer(
+                            &contract_addr,
+                            &holder,
+                            &amount,
+                        );
+                        paid.push_back((holder, amount));
+                    }
+                }
+                Err(RevoraError::HolderBlacklisted) => skipped_blacklisted.push_back(holder),
+                Err(RevoraError::NotWhitelisted) => skipped_not_whitelisted.push_back(holder),
+                Err(RevoraError::HolderSanctioned) => skipped_sanctioned.push_back(holder),
+                // Nothing pending, or the chosen period was already paid or is not theirs
+                Err(RevoraError::NoPendingClaims) | Err(RevoraError::PeriodNotClaimable) => {}
+                Err(err) => failed.push_back((holder, err as u32)),
+            }
+        }
+
+        env.events().publish(
+            (EVENT_PUSH, caller, token),
+            (
+                paid.len(),
+                skipped_blacklisted.len(),
+                skipped_not_whitelisted.len(),
+                skipped_sanctioned.len(),
+                failed.len(),
+                next_cursor,
+            ),
+        );
+        Ok(PushDistributionResult {
+            paid,
+            skipped_blacklisted,
+            skipped_not_whitelisted,
+            skipped_sanctioned,
+            failed,
+            next_cursor,
+        })
+    }
+
+    /// Add holders to the registry that `push_distribution` pages through.
+    ///
+    /// Holders are registered on their first `set_holder_share`; this backfills
+    /// holders whose share was set before the registry existed. Addresses that are
+    /// already registered or never held a share are skipped. At most 20 per call.
+    ///
+    /// ### Returns
+    /// - `Ok(count)` with the number of holders newly registered.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the keeper.
+    /// - `Err(RevoraError::LimitReached)` if more than 20 holders are listed.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn register_holders(
+        env: Env,
+        caller: Address,
+        token: Address,
+        holders: Vec<Address>,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_issuer_or_keeper(&env, &caller, &token)?;
+        caller.require_auth();
+        if holders.len() > MAX_PAGE_LIMIT {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let mut added = 0;
+        for holder in holders.iter() {
+            if Self::is_registered_holder(&env, &token, &holder)
+                || !Self::has_share_history(&env, &token, &holder)
+            {
+                continue;
+            }
+            Self::register_holder(&env, &token, &holder);
+            added += 1;
+        }
+        env.events().publish((EVENT_HOLDERS_REG, caller, token), added);
+        Ok(added)
+    }
+
+    /// `caller` must be the offering's current issuer or its keeper.
+    fn require_issuer_or_keeper(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        let current_issuer =
+            Self::get_current_issuer(env, token).ok_or(RevoraError::OfferingNotFound)?;
+        if *caller != current_issuer
+            && Some(caller.clone()) != Self::get_keeper(env.clone(), token.clone())
+        {
+            return Err(RevoraError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    /// Return the number of holders ever assigned a share for an offering.
+    pub fn get_holder_count(env: Env, token: Address) -> u32 {
+        env.storage().persistent().get(&DistributionKey::HolderCount(token)).unwrap_or(0)
+    }
+
+    /// Return a page of registered holders. Limit capped at MAX_PAGE_LIMIT (20).
+    /// Ordering: by first share assignment, deterministic.
+    pub fn get_holders_page(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        let count = Self::get_holder_count(env.clone(), token.clone());
+
+        let effective_limit =
+            if limit == 0 || limit > MAX_PAGE_LIMIT { MAX_PAGE_LIMIT } else { limit };
+
+        if start >= count {
+            return (Vec::new(&env), None);
+        }
+
+        let end = core::cmp::min(start + effective_limit, count);
+        let mut results = Vec::new(&env);
+        for i in start..end {
+            let holder_key = DistributionKey::HolderAt(token.clone(), i);
+            results.push_back(env.storage().persistent().get(&holder_key).unwrap());
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    fn is_registered_holder(env: &Env, token: &Address, holder: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DistributionKey::HolderRegistered(token.clone(), holder.clone()))
+    }
+
+    fn register_holder(env: &Env, token: &Address, holder: &Address) {
+        let count = Self::get_holder_count(env.clone(), token.clone());
+        let holder_key = DistributionKey::HolderAt(token.clone(), count);
+        env.storage().persistent().set(&holder_key, holder);
+        env.storage()
+            .persistent()
+            .set(&DistributionKey::HolderRegistered(token.clone(), holder.clone()), &true);
+        env.storage().persistent().set(&DistributionKey::HolderCount(token.clone()), &(count + 1));
+    }
+
+    // ── Accumulator mode ──────────────────────────────────────
+
+    /// Switch an offering between period-list claims and the revenue-per-bps accumulator.
+    ///
+    /// In accumulator mode each deposit adds `amount / 10000` per bps to a running
+    /// index, and a holder's claim pays `share_bps * (index - checkpoint)` in one step,
+    /// however many periods are pending. Holders are settled whenever their share
+    /// changes. Claim events carry only the total (`claim_acc`); use period-list mode
+    /// when per-period claim detail, claim delays or deadlines, or back-period grants are needed.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::DistributionModeConflict)` if revenue was already deposited, a
+    ///   claim delay, deadline or release schedule is set, the share precision is not bps, or
+    ///   token hooks or TWAB entitlement are enabled.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_accumulator_mode(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        enabled: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        if Self::get_period_count(env.clone(), token.clone()) > 0
+            || Self::get_claim_delay(env.clone(), token.clone()) > 0
+            || Self::get_release_schedule(env.clone(), token.clone()) != ReleaseSchedule::default()
+            || Self::get_claim_deadline(env.clone(), token.clone()) > 0
+            || Self::get_share_precision(env.clone(), token.clone()) != SharePrecision::Bps
+            || Self::has_token_hooks(env.clone(), token.clone())
+            || Self::get_twab_config(env.clone(), token.clone())
+        {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+
+        env.storage().persistent().set(&AccountingKey::AccumulatorMode(token.clone()), &enabled);
+        env.events().publish((EVENT_ACC_MODE, issuer, token), enabled);
+        Ok(())
+    }
+
+    /// Return true if the offering's claims use the revenue-per-bps accumulator.
+    pub fn is_accumulator_mode(env: Env, token: Address) -> bool {
+        env.storage().persistent().get(&AccountingKey::AccumulatorMode(token)).unwrap_or(false)
+    }
+
+    /// Add a deposit to the accumulator index. Bps offerings spread it over 10000 bps,
+    /// of which holders are allocated at most `revenue_share_bps`; token-hooked
+    /// offerings spread the revenue share of it over the token supply, carrying the
+    /// division remainder (all of it while the supply is 0) into the next deposit.
+    fn accumulate_revenue(env: &Env, token: &Address, revenue: i128) -> Result<(), RevoraError> {
+        let acc_key = AccountingKey::RevenuePerBps(token.clone());
+        let acc: i128 = env.storage().persistent().get(&acc_key).unwrap_or(0);
+        let acc_delta = if Self::has_token_hooks(env.clone(), token.clone()) {
+            let carry_key = AccountingKey::AccCarry(token.clone());
+            let carry: i128 = env.storage().persistent().get(&carry_key).unwrap_or(0);
+            // ACC_SCALE is a multiple of BPS_DENOMINATOR, so this is exact
+            let bps = Self::offering_share_bps(env, token) as i128;
+            let scaled = revenue
+                .checked_mul(ACC_SCALE / BPS_DENOMINATOR * bps)
+                .and_then(|r| r.checked_add(carry))
+                .ok_or(RevoraError::ArithmeticOverflow)?;
+            let supply = Self::total_allocated_units(env, token);
+            if supply == 0 {
+                env.storage().persistent().set(&carry_key, &scaled);
+                return Ok(());
+            }
+            env.storage().persistent().set(&carry_key, &(scaled % supply));
+            scaled / supply
+        } else {
+            Self::mul_div_rounded(revenue, ACC_SCALE, BPS_DENOMINATOR, RoundingMode::Truncation)?
+        };
+        let acc = acc.checked_add(acc_delta).ok_or(RevoraError::ArithmeticOverflow)?;
+        env.storage().persistent().set(&acc_key, &acc);
+        Ok(())
+    }
+
+    // ── Token transfer hooks ──────────────────────────────────
+
+    /// Let the offering token keep holder shares in step with balances.
+    ///
+    /// Puts the offering in accumulator mode with each holder's share equal to their
+    /// token balance, so the offering's revenue share of a deposit is spread over the
+    /// token's supply. The token must
+    /// call `on_token_transfer` on every transfer, mint and burn; `set_holder_share`
+    /// is then rejected. Cannot be undone.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::DistributionModeConflict)` if revenue was already deposited, a
+    ///   holder share, claim delay, deadline or release schedule is set, the share precision is not
+    ///   bps, or TWAB entitlement is enabled.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn enable_token_hooks(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        if Self::get_period_count(env.clone(), token.clone()) > 0
+            || Self::get_holder_count(env.clone(), token.clone()) > 0
+            || Self::get_claim_delay(env.clone(), token.clone()) > 0
+            || Self::get_release_schedule(env.clone(), token.clone()) != ReleaseSchedule::default()
+            || Self::get_claim_deadline(env.clone(), token.clone()) > 0
+            || Self::get_share_precision(env.clone(), token.clone()) != SharePrecision::Bps
+            || Self::get_twab_config(env.clone(), token.clone())
+        {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+
+        env.storage().persistent().set(&AccountingKey::AccumulatorMode(token.clone()), &true);
+        env.storage().persistent().set(&DistributionKey::TokenHooks(token.clone()), &true);
+        env.events().publish((EVENT_TOKEN_HOOKS, issuer, token), ());
+        Ok(())
+    }
+
+    /// Return true if the offering's holder shares follow token balances.
+    pub fn has_token_hooks(env: Env, token: Address) -> bool {
+        env.storage().persistent().get(&DistributionKey::TokenHooks(token)).unwrap_or(false)
+    }
+
+    /// Transfer hook, called by the offering token itself: `from = None` for a mint,
+    /// `to = None` for a burn. Settles both holders' accrued revenue, then moves
+    /// `amount` of share from `from` to `to`. Not blocked by freeze or pause, so the
+    /// token stays transferable.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if `token` has no offering.
+    /// - `Err(RevoraError::DistributionModeConflict)` if token hooks are not enabled.
+    /// - `Err(RevoraError::InvalidAmount)` if `amount` is negative or exceeds `from`'s share.
+    /// - `Err(RevoraError::HolderSanctioned)` if `to` is sanctioned.
+    pub fn on_token_transfer(
+        env: Env,
+        token: Address,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: i128,
+    ) -> Result<(), RevoraError> {
+        Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        token.require_auth();
+        if !Self::has_token_hooks(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+        Self::require_non_negative_amount(amount)?;
+
+        // Balances are kept in share units (see `uses_share_units`)
+        let total_key = DistributionKey::TotalAllocatedUnits(token.clone());
+        let mut supply: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        match &from {
+            Some(from) => {
+                let balance =
+                    Self::get_holder_share_units(env.clone(), token.clone(), from.clone());
+                if amount > balance {
+                    return Err(RevoraError::InvalidAmount);
+                }
+                Self::settle_accumulator(&env, &token, from)?;
+                let key = DistributionKey::HolderShareUnits(token.clone(), from.clone());
+                env.storage().persistent().set(&key, &(balance - amount));
+            }
+            None => supply = supply.checked_add(amount).ok_or(RevoraError::ArithmeticOverflow)?,
+        }
+        match &to {
+            Some(to) => {
+                if Self::is_sanctioned(env.clone(), to.clone()) {
+                    return Err(RevoraError::HolderSanctioned);
+                }
+                let key = DistributionKey::HolderShareUnits(token.clone(), to.clone());
+                let balance: Option<i128> = env.storage().persistent().get(&key);
+                if balance.is_none() {
+                    Self::register_holder(&env, &token, to);
+                }
+                Self::settle_accumulator(&env, &token, to)?;
+                env.storage().persistent().set(&key, &(balance.unwrap_or(0) + amount));
+            }
+            None => supply -= amount,
+        }
+        env.storage().persistent().set(&total_key, &supply);
+
+        env.events().publish((EVENT_SHARE_SYNC, token), (from, to, amount));
+        Ok(())
+    }
+
+    /// Accumulator-mode `book_pending_periods`: settles and clears the holder's accrued
+    /// revenue. The caller settles the liability and emits the event.
+    fn book_accumulated_claim(
+        env: &Env,
+        holder: &Address,
+        token: &Address,
+    ) -> Result<(i128, Address), RevoraError> {
+        if Self::accumulated_claimable(env, token, holder)? == 0 {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        let payout = Self::settle_accumulator(env, token, holder)?;
+        let accrued_key = AccountingKey::HolderAccrued(token.clone(), holder.clone());
+        env.storage().persistent().remove(&accrued_key);
+
+        let pt_key = DataKey::PaymentToken(token.clone());
+        let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+        Ok((payout, payment_token))
+    }
+
+    /// Move everything the holder earned since their last checkpoint into their accrued
+    /// balance at the current share, and return the new accrued balance. Must run
+    /// before the holder's share changes.
+    fn settle_accumulator(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+    ) -> Result<i128, RevoraError> {
+        let accrued = Self::accumulated_claimable(env, token, holder)?;
+        let acc_key = AccountingKey::RevenuePerBps(token.clone());
+        let acc: i128 = env.storage().persistent().get(&acc_key).unwrap_or(0);
+        let cp_key = AccountingKey::HolderAccCheckpoint(token.clone(), holder.clone());
+        env.storage().persistent().set(&cp_key, &acc);
+        let accrued_key = AccountingKey::HolderAccrued(token.clone(), holder.clone());
+        env.storage().persistent().set(&accrued_key, &accrued);
+
+        // Keeps get_pending_periods in step: everything deposited so far is settled
+        let period_count = Self::get_period_count(env.clone(), token.clone());
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
+        env.storage().persistent().set(&idx_key, &period_count);
+        Ok(accrued)
+    }
+
+    fn accumulated_claimable(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+    ) -> Result<i128, RevoraError> {
+        let acc_key = AccountingKey::RevenuePerBps(token.clone());
+        let acc: i128 = env.storage().persistent().get(&acc_key).unwrap_or(0);
+        let cp_key = AccountingKey::HolderAccCheckpoint(token.clone(), holder.clone());
+        let checkpoint: i128 = env.storage().persistent().get(&cp_key).unwrap_or(0);
+        let accrued_key = AccountingKey::HolderAccrued(token.clone(), holder.clone());
+        let accrued: i128 = env.storage().persistent().get(&accrued_key).unwrap_or(0);
+        // Accumulator offerings use bps, or token balances with hooks (see `accumulate_revenue`)
+        let share = Self::get_holder_share_units(env.clone(), token.clone(), holder.clone());
+        // Always truncates: per-holder rounding up could over-distribute in aggregate
+        let earned =
+            Self::mul_div_rounded(share, acc - checkpoint, ACC_SCALE, RoundingMode::Truncation)?;
+        accrued.checked_add(earned).ok_or(RevoraError::ArithmeticOverflow)
+    }
+
+    // ── Claim deadlines and sweeps ────────────────────────────
+
+    /// Set how long after its deposit a period stays claimable (0 = forever).
+    ///
+    /// The deadline is fixed per period when it is deposited; changing it only affects
+    /// later deposits. Once a period's deadline passes, `claim` and `get_claimable` skip
+    /// it and its unclaimed revenue can be recovered with `sweep_expired_period`.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::DistributionModeConflict)` if the offering uses accumulator mode.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_claim_deadline(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        deadline_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        if deadline_secs > 0 && Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+        let key = DistributionKey::ClaimDeadlineSecs(token.clone());
+        env.storage().persistent().set(&key, &deadline_secs);
+        env.events().publish((EVENT_CLAIM_DEADLINE_SET, issuer, token), deadline_secs);
+        Ok(())
+    }
+
+    /// Get the per-offering claim deadline in seconds after deposit. 0 = no deadline.
+    pub fn get_claim_deadline(env: Env, token: Address) -> u64 {
+        let key = DistributionKey::ClaimDeadlineSecs(token);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Set or clear the address that receives swept revenue. Defaults to the issuer.
+    pub fn set_treasury(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        treasury: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = DistributionKey::Treasury(token.clone());
+        match &treasury {
+            Some(addr) => env.storage().persistent().set(&key, addr),
+            None => env.storage().persistent().remove(&key),
+        }
+        env.events().publish((EVENT_TREASURY_SET, issuer, token), treasury);
+        Ok(())
+    }
+
+    /// Return the configured treasury for an offering, if any.
+    pub fn get_treasury(env: Env, token: Address) -> Option<Address> {
+        env.storage().persistent().get(&DistributionKey::Treasury(token))
+    }
+
+    /// Transfer the unclaimed revenue of an expired period to the treasury (or issuer).
+    ///
+    /// The amount is the period's deposit minus everything already claimed from it
+    /// and any dust settled for it. A period can be swept once.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The amount swept.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::InvalidPeriodId)` if nothing was deposited for `period_id`.
+    /// - `Err(RevoraError::PeriodNotExpired)` if the period has no deadline or it has not passed.
+    /// - `Err(RevoraError::PeriodAlreadySwept)` if the period was already swept.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn sweep_expired_period(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let revenue: i128 =
+            env.storage().persistent().get(&rev_key).ok_or(RevoraError::InvalidPeriodId)?;
+        if !Self::is_period_expired(&env, &token, period_id) {
+            return Err(RevoraError::PeriodNotExpired);
+        }
+        let swept_key = DistributionKey::PeriodSwept(token.clone(), period_id);
+        if env.storage().persistent().has(&swept_key) {
+            return Err(RevoraError::PeriodAlreadySwept);
+        }
+
+        let claimed: i128 = if Self::is_merkle_period(&env, &token, period_id) {
+            let merkle_key = DataKey::MerkleClaimedTotal(token.clone(), period_id);
+            env.storage().persistent().get(&merkle_key).unwrap_or(0)
+        } else {
+            let claimed_key = AccountingKey::PeriodClaimed(token.clone(), period_id);
+            let paid: i128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+            // Settled dust stays in the dust ledger
+            paid + Self::get_period_dust(env.clone(), token.clone(), period_id).unwrap_or(0)
+        };
+        let redistributed: i128 =
+            Self::get_redistributed(&env, &token, period_id).iter().map(|(amount, _)| amount).sum();
+        let amount = revenue + redistributed - claimed;
+        env.storage().persistent().set(&swept_key, &true);
+        // Closes the period for every claim path, whatever the deadline is later set to
+        env.storage()
+            .persistent()
+            .set(&DistributionKey::PeriodDeadline(token.clone(), period_id), &0_u64);
+
+        let recipient = Self::get_treasury(env.clone(), token.clone()).unwrap_or(issuer.clone());
+        if amount > 0 {
+            let pt_key = DataKey::PaymentToken(token.clone());
+            let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &amount,
+            );
+            Self::add_to_counter(&env, &AccountingKey::TotalSwept(token.clone()), amount);
+            Self::add_to_counter(&env, &AccountingKey::AssetLiability(payment_token), -amount);
+        }
+
+        env.events().publish((EVENT_SWEEP, issuer, token), (period_id, recipient, amount));
+        Ok(amount)
+    }
+
+    /// True if `period_id` was swept or the deadline fixed at its deposit has passed.
+    fn is_period_expired(env: &Env, token: &Address, period_id: u64) -> bool {
+        // Only periods deposited with a deadline can expire or be swept
+        if !env.storage().persistent().has(&DistributionKey::HasPeriodDeadlines(token.clone())) {
+            return false;
+        }
+        let deadline_key = DistributionKey::PeriodDeadline(token.clone(), period_id);
+        match env.storage().persistent().get::<DistributionKey, u64>(&deadline_key) {
+            Some(deadline) => env.ledger().timestamp() >= deadline,
+            None => false,
+        }
+    }
+
+    // ── Blacklisted revenue ───────────────────────────────────
+
+    /// Set what `settle_blacklisted_revenue` does with a blacklisted holder's revenue.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_blacklist_policy(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        policy: BlacklistPolicy,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        env.storage().persistent().set(&DistributionKey::BlacklistPolicy(token.clone()), &policy);
+        env.events().publish((EVENT_BL_POLICY, issuer, token), policy);
+        Ok(())
+    }
+
+    /// Get an offering's blacklist policy (defaults to `Escrow`).
+    pub fn get_blacklist_policy(env: Env, token: Address) -> BlacklistPolicy {
+        env.storage().persistent().get(&DistributionKey::BlacklistPolicy(token)).unwrap_or_default()
+    }
+
+    /// Take a blacklisted or sanctioned holder's revenue out of their pending periods, as
+    /// `claim` would for up to `max_periods` periods, and apply the offering's blacklist
+    /// policy to it:
+    /// - `Escrow`: held for the holder and paid to them when `blacklist_remove`,
+    ///   `whitelist_add` or `sanction_remove` makes them eligible again, or when they
+    ///   call `claim_escrow`.
+    /// - `Redistribute`: put back into its periods for the holders not yet paid from
+    ///   them, pro rata (see `redistribute_revenue`).
+    /// - `ReturnToIssuer`: transferred to the issuer and booked as swept.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The amount settled.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::NotAuthorized)` if `caller` is neither the issuer nor the admin.
+    /// - `Err(RevoraError::HolderNotBlacklisted)` if the holder is neither blacklisted
+    ///   nor sanctioned.
+    /// - `Err(RevoraError::NoRemainingHolders)` under `Redistribute` if one of the
+    ///   periods has no other holder left to pay.
+    /// - Otherwise the errors of `claim`.
+    pub fn settle_blacklisted_revenue(
+        env: Env,
+        caller: Address,
+        token: Address,
+        holder: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        Self::require_issuer_or_admin(&env, &caller, &token)?;
+        if !Self::is_blacklisted(env.clone(), token.clone(), holder.clone())
+            && !Self::is_sanctioned(env.clone(), holder.clone())
+        {
+            return Err(RevoraError::HolderNotBlacklisted);
+        }
+
+        let (amount, payment_token, booked) =
+            if Self::is_accumulator_mode(env.clone(), token.clone()) {
+                let (amount, payment_token) = Self::book_accumulated_claim(&env, &holder, &token)?;
+                (amount, payment_token, Vec::new(&env))
+            } else {
+                Self::book_pending_periods(&env, &holder, &token, max_periods)?
+            };
+        let mut periods = Vec::new(&env);
+        for (period_id, _) in booked.iter() {
+            periods.push_back(period_id);
+        }
+
+        let policy = Self::get_blacklist_policy(env.clone(), token.clone());
+        if amount > 0 {
+            match policy {
+                BlacklistPolicy::Escrow => {
+                    let key = AccountingKey::Escrowed(token.clone(), holder.clone());
+                    Self::add_to_counter(&env, &key, amount);
+                    let tokens_key = AccountingKey::EscrowedTokens(holder.clone());
+                    let mut tokens: Vec<Address> = env
+                        .storage()
+                        .persistent()
+                        .get(&tokens_key)
+                        .unwrap_or_else(|| Vec::new(&env));
+                    if !tokens.contains(&token) {
+                        tokens.push_back(token.clone());
+                        env.storage().persistent().set(&tokens_key, &tokens);
+                    }
+                }
+                BlacklistPolicy::Redistribute => {
+                    Self::redistribute_revenue(&env, &token, &holder, amount, &booked)?;
+                }
+                BlacklistPolicy::ReturnToIssuer => {
+                    let issuer = Self::get_current_issuer(&env, &token).unwrap();
+                    token::Client::new(&env, &payment_token).transfer(
+                        &env.current_contract_address(),
+                        &issuer,
+                        &amount,
+                    );
+                    Self::add_to_counter(&env, &AccountingKey::TotalSwept(token.clone()), amount);
+                    let liability_key = AccountingKey::AssetLiability(payment_token);
+                    Self::add_to_counter(&env, &liability_key, -amount);
+                }
+            }
+        }
+
+        env.events().publish((EVENT_BL_SETTLED, caller, token, holder), (policy, amount, periods));
+        Ok(amount)
+    }
+
+    /// Return the revenue escrowed for a blacklisted or sanctioned holder and not yet
+    /// released.
+    pub fn get_escrowed_revenue(env: Env, token: Address, holder: Address) -> i128 {
+        env.storage().persistent().get(&AccountingKey::Escrowed(token, holder)).unwrap_or(0)
+    }
+
+    /// Claim revenue escrowed while the holder was blacklisted or sanctioned, once they
+    /// are eligible for payouts again. Escrow is released automatically by
+    /// `blacklist_remove`, `whitelist_add` and `sanction_remove`; this covers a blacklist
+    /// entry that expired or offerings `sanction_remove` did not reach.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The amount paid.
+    /// - `Err(RevoraError::HolderSanctioned)`, `HolderBlacklisted` or `NotWhitelisted` if
+    ///   the holder is still not eligible.
+    /// - `Err(RevoraError::NoPendingClaims)` if nothing is escrowed for the holder.
+    pub fn claim_escrow(env: Env, holder: Address, token: Address) -> Result<i128, RevoraError> {
+        holder.require_auth();
+        Self::require_payout_eligible(&env, &token, &holder)?;
+        let amount = Self::get_escrowed_revenue(env.clone(), token.clone(), holder.clone());
+        if amount == 0 {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        Self::pay_escrow(&env, &token, &holder, amount);
+        Ok(amount)
+    }
+
+    /// Pay `holder` their escrowed revenue if they are eligible for payouts again.
+    fn release_escrow(env: &Env, token: &Address, holder: &Address) {
+        let amount = Self::get_escrowed_revenue(env.clone(), token.clone(), holder.clone());
+        if amount == 0 || Self::require_payout_eligible(env, token, holder).is_err() {
+            return;
+        }
+        Self::pay_escrow(env, token, holder, amount);
+    }
+
+    fn pay_escrow(env: &Env, token: &Address, holder: &Address, amount: i128) {
+        env.storage().persistent().remove(&AccountingKey::Escrowed(token.clone(), holder.clone()));
+        let tokens_key = AccountingKey::EscrowedTokens(holder.clone());
+        if let Some(mut tokens) = env.storage().persistent().get::<_, Vec<Address>>(&tokens_key) {
+            if let Some(i) = tokens.first_index_of(token) {
+                tokens.remove(i);
+            }
+            if tokens.is_empty() {
+                env.storage().persistent().remove(&tokens_key);
+            } else {
+                env.storage().persistent().set(&tokens_key, &tokens);
+            }
+        }
+
+        let pt_key = DataKey::PaymentToken(token.clone());
+        let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+        token::Client::new(env, &payment_token).transfer(
+            &env.current_contract_address(),
+            holder,
+            &amount,
+        );
+        Self::record_payout(env, token, &payment_token, amount);
+        env.events().publish((EVENT_ESCROW_RELEASED, token.clone(), holder.clone()), amount);
+    }
+
+    /// Share `amount` of `holder`'s revenue among the offering's other holders, pro rata
+    /// to their shares. Accumulator offerings add it to the index over the other holders'
+    /// current shares and move `holder`'s checkpoint past it. Otherwise each booked
+    /// period's payout goes back into that period, split over the summed share of the
+    /// holders not yet paid from it (see `period_share_total`), and is paid with their
+    /// claims on the period. Emits `bl_redist` per period, with period 0 in accumulator
+    /// mode.
+    fn redistribute_revenue(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        amount: i128,
+        booked: &BookedPeriods,
+    ) -> Result<(), RevoraError> {
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            let held = Self::get_holder_share_units(env.clone(), token.clone(), holder.clone());
+            let others = Self::total_allocated_units(env, token) - held;
+            if others <= 0 {
+                return Err(RevoraError::NoRemainingHolders);
+            }
+            let acc_key = AccountingKey::RevenuePerBps(token.clone());
+            let acc: i128 = env.storage().persistent().get(&acc_key).unwrap_or(0);
+            let delta = Self::mul_div_rounded(amount, ACC_SCALE, others, RoundingMode::Truncation)?;
+            let acc = acc.checked_add(delta).ok_or(RevoraError::ArithmeticOverflow)?;
+            env.storage().persistent().set(&acc_key, &acc);
+            let cp_key = AccountingKey::HolderAccCheckpoint(token.clone(), holder.clone());
+            env.storage().persistent().set(&cp_key, &acc);
+            env.events().publish((EVENT_BL_REDIST, token.clone(), holder.clone()), (0_u64, amount));
+            return Ok(());
+        }
+
+        for (period_id, payout) in booked.iter() {
+            if payout == 0 {
+                continue;
+            }
+            // `holder` was just booked, so a settled share of theirs is already paid
+            let paid_key = AccountingKey::PeriodPaidShare(token.clone(), period_id);
+            let (paid_share, _): (i128, i128) =
+                env.storage().persistent().get(&paid_key).unwrap_or((0, 0));
+            let unpaid = Self::period_share_total(env, token, period_id)? - paid_share;
+            if unpaid <= 0 {
+                return Err(RevoraError::NoRemainingHolders);
+            }
+            let mut pieces = Self::get_redistributed(env, token, period_id);
+            pieces.push_back((payout, unpaid));
+            env.storage()
+                .persistent()
+                .set(&DistributionKey::Redistributed(token.clone(), period_id), &pieces);
+            env.events()
+                .publish((EVENT_BL_REDIST, token.clone(), holder.clone()), (period_id, payout));
+        }
+        env.storage().persistent().set(&DistributionKey::HasRedistributions(token.clone()), &true);
+        Ok(())
+    }
+
+    /// Summed share of all holders in a period, in the units `holder_period_share`
+    /// returns: the snapshot supply scaled by `revenue_share_bps` for snapshot periods,
+    /// the shares allocated at deposit times the window for TWAB periods (exact only
+    /// if shares did not change within the window), and the shares allocated at
+    /// deposit otherwise.
+    fn period_share_total(env: &Env, token: &Address, period_id: u64) -> Result<i128, RevoraError> {
+        if let Some(ledger) = Self::get_period_snapshot(env.clone(), token.clone(), period_id) {
+            let supply = SnapshotTokenClient::new(env, token).total_supply_at(&ledger);
+            return Ok(Self::snapshot_share(env, token, supply, 1)?.0);
+        }
+        let allocated: i128 = env
+            .storage()
+            .persistent()
+            .get(&DistributionKey::PeriodAllocated(token.clone(), period_id))
+            .unwrap_or_else(|| Self::total_allocated_units(env, token));
+        match Self::get_period_twab_window(env.clone(), token.clone(), period_id) {
+            Some((start, end)) if end > start => {
+                allocated.checked_mul((end - start) as i128).ok_or(RevoraError::ArithmeticOverflow)
+            }
+            _ => Ok(allocated),
+        }
+    }
+
+    // ── Rounding dust ─────────────────────────────────────────
+
+    /// Settle a period's rounding remainder into the offering's dust ledger.
+    ///
+    /// The dust is what rounding left over from the holders paid so far: the period's
+    /// revenue times their summed shares, less what they were paid. Revenue of
+    /// unallocated shares and of holders who have not claimed yet is not dust. Can be
+    /// called again as more holders claim; each call settles the remainder accrued
+    /// since the last one.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The dust settled (may be 0).
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::InvalidPeriodId)` if nothing was deposited for `period_id`.
+    /// - `Err(RevoraError::DistributionModeConflict)` for Merkle/TWAB periods, periods
+    ///   holding redistributed revenue or accumulator mode.
+    /// - `Err(RevoraError::PeriodAlreadySwept)` if the period was already swept.
+    /// - `Err(RevoraError::DustAlreadySettled)` if the period was settled before and no
+    ///   new remainder has accrued since.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn settle_period_dust(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let revenue: i128 =
+            env.storage().persistent().get(&rev_key).ok_or(RevoraError::InvalidPeriodId)?;
+        if Self::is_accumulator_mode(env.clone(), token.clone())
+            || Self::is_merkle_period(&env, &token, period_id)
+            || Self::get_period_twab_window(env.clone(), token.clone(), period_id).is_some()
+            || env
+                .storage()
+                .persistent()
+                .has(&DistributionKey::Redistributed(token.clone(), period_id))
+        {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+        if env.storage().persistent().has(&DistributionKey::PeriodSwept(token.clone(), period_id)) {
+            return Err(RevoraError::PeriodAlreadySwept);
+        }
+        let dust_key = AccountingKey::PeriodDust(token.clone(), period_id);
+        let settled: Option<i128> = env.storage().persistent().get(&dust_key);
+
+        let paid_key = AccountingKey::PeriodPaidShare(token.clone(), period_id);
+        let (paid_share, paid): (i128, i128) =
+            env.storage().persistent().get(&paid_key).unwrap_or((0, 0));
+        // Paid shares of a snapshot period are balances scaled by revenue_share_bps
+        let denominator = match Self::get_period_snapshot(env.clone(), token.clone(), period_id) {
+            Some(ledger) => {
+                let supply = SnapshotTokenClient::new(&env, &token).total_supply_at(&ledger);
+                Self::snapshot_share(&env, &token, 0, core::cmp::max(supply, 1))?.1
+            }
+            None => Self::share_scale(&env, &token),
+        };
+        let owed =
+            Self::mul_div_rounded(revenue, paid_share, denominator, RoundingMode::Truncation)?;
+        // Round-up modes can pay more than the exact share; that is never dust
+        let dust = core::cmp::max(owed - paid - settled.unwrap_or(0), 0);
+        if dust == 0 && settled.is_some() {
+            return Err(RevoraError::DustAlreadySettled);
+        }
+
+        env.storage().persistent().set(&dust_key, &(settled.unwrap_or(0) + dust));
+        if dust > 0 {
+            let pt_key = DataKey::PaymentToken(token.clone());
+            let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+            Self::add_to_counter(&env, &AccountingKey::DustPending(token.clone()), dust);
+            Self::add_to_counter(&env, &AccountingKey::AssetDust(payment_token.clone()), dust);
+            Self::add_to_counter(&env, &AccountingKey::AssetLiability(payment_token), -dust);
+        }
+
+        env.events().publish((EVENT_DUST_SETTLED, issuer, token), (period_id, dust));
+        Ok(dust)
+    }
+
+    /// Transfer the offering's pending dust to the issuer.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The amount reclaimed (0 if nothing is pending).
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn reclaim_dust(env: Env, issuer: Address, token: Address) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let pending_key = AccountingKey::DustPending(token.clone());
+        let amount: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+        if amount > 0 {
+            let pt_key = DataKey::PaymentToken(token.clone());
+            let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+            env.storage().persistent().set(&pending_key, &0_i128);
+            Self::add_to_counter(&env, &AccountingKey::DustReclaimed(token.clone()), amount);
+            Self::add_to_counter(&env, &AccountingKey::AssetDust(payment_token.clone()), -amount);
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &issuer,
+                &amount,
+            );
+        }
+
+        env.events().publish((EVENT_DUST_RECLAIMED, issuer, token), amount);
+        Ok(amount)
+    }
+
+    /// Roll the offering's pending dust into its next deposit, where it is shared
+    /// among holders like the deposited revenue. Merkle deposits do not take rolled dust.
+    ///
+    /// ### Returns
+    /// - `Ok(i128)` The amount rolled forward (0 if nothing is pending).
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn roll_dust_forward(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let pending_key = AccountingKey::DustPending(token.clone());
+        let amount: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+        if amount > 0 {
+            env.storage().persistent().set(&pending_key, &0_i128);
+            Self::add_to_counter(&env, &AccountingKey::DustRolled(token.clone()), amount);
+        }
+
+        env.events().publish((EVENT_DUST_ROLLED, issuer, token), amount);
+        Ok(amount)
+    }
+
+    /// Return the dust settled for a period, or `None` if it has not been settled.
+    pub fn get_period_dust(env: Env, token: Address, period_id: u64) -> Option<i128> {
+        env.storage().persistent().get(&AccountingKey::PeriodDust(token, period_id))
+    }
+
+    /// Return the offering's dust ledger: pending, rolled forward and reclaimed.
+    pub fn get_dust_ledger(env: Env, token: Address) -> DustLedger {
+        let storage = env.storage().persistent();
+        DustLedger {
+            pending: storage.get(&AccountingKey::DustPending(token.clone())).unwrap_or(0),
+            rolled: storage.get(&AccountingKey::DustRolled(token.clone())).unwrap_or(0),
+            reclaimed: storage.get(&AccountingKey::DustReclaimed(token)).unwrap_or(0),
+        }
+    }
+
+    /// Return the dust held in `payment_token` across all offerings (pending plus rolled).
+    /// Together with outstanding liabilities and accrued platform fees this accounts for
+    /// the contract's whole balance of the token.
+    pub fn get_total_dust(env: Env, payment_token: Address) -> i128 {
+        env.storage().persistent().get(&AccountingKey::AssetDust(payment_token)).unwrap_or(0)
+    }
+
+    /// Move the offering's rolled dust back into holder liabilities; returns the amount
+    /// to add to the period being deposited.
+    fn apply_rolled_dust(env: &Env, token: &Address, payment_token: &Address) -> i128 {
+        let rolled_key = AccountingKey::DustRolled(token.clone());
+        let rolled: i128 = env.storage().persistent().get(&rolled_key).unwrap_or(0);
+        if rolled > 0 {
+            env.storage().persistent().set(&rolled_key, &0_i128);
+            Self::add_to_counter(env, &AccountingKey::AssetDust(payment_token.clone()), -rolled);
+            let liability_key = AccountingKey::AssetLiability(payment_token.clone());
+            Self::add_to_counter(env, &liability_key, rolled);
+        }
+        rolled
+    }
+
+    // ── Solvency accounting ───────────────────────────────────
+
+    /// Return deposited, claimed and outstanding totals for an offering.
+    pub fn get_offering_liabilities(env: Env, token: Address) -> OfferingLiabilities {
+        let total_deposited: i128 = env
+            .storage()
+            .persistent()
+            .get(&AccountingKey::TotalDeposited(token.clone()))
+            .unwrap_or(0);
+        let total_claimed: i128 = env
+            .storage()
+            .persistent()
+            .get(&AccountingKey::TotalClaimed(token.clone()))
+            .unwrap_or(0);
+        let total_swept: i128 =
+            env.storage().persistent().get(&AccountingKey::TotalSwept(token.clone())).unwrap_or(0);
+        let ledger = Self::get_dust_ledger(env, token);
+        let dust = ledger.pending + ledger.rolled;
+        OfferingLiabilities {
+            total_deposited,
+            total_claimed,
+            total_swept,
+            total_dust_reclaimed: ledger.reclaimed,
+            dust,
+            outstanding: total_deposited - total_claimed - total_swept - ledger.reclaimed - dust,
+        }
+    }
 
-        env.events()
-            .publish((EVENT_REV_DEPOSIT, issuer, token), (payment_token, amount, period_id));
-        let key = DataKey::SnapshotConfig(issuer.clone(), token.clone());
-        env.storage().persistent().set(&key, &enabled);
+    /// Compare the outstanding liabilities of every offering paid in `payment_token`
+    /// with the contract's actual balance of that token. A non-zero `shortfall`
+    /// means holders cannot all be paid.
+    pub fn get_solvency(env: Env, payment_token: Address) -> SolvencyReport {
+        let liability_key = AccountingKey::AssetLiability(payment_token.clone());
+        let owed_to_holders: i128 = env.storage().persistent().get(&liability_key).unwrap_or(0);
+        let liabilities = owed_to_holders
+            + Self::get_platform_fees_accrued(env.clone(), payment_token.clone())
+            + Self::get_total_dust(env.clone(), payment_token.clone());
+        let balance =
+            token::Client::new(&env, &payment_token).balance(&env.current_contract_address());
+        SolvencyReport { liabilities, balance, shortfall: core::cmp::max(liabilities - balance, 0) }
+    }
 
-        env.events()
-            .publish((EVENT_SNAP_CONFIG, issuer, token), enabled);
-        Ok(())
+    /// Book a deposit as owed to the offering's holders.
+    fn record_liability(env: &Env, token: &Address, payment_token: &Address, amount: i128) {
+        Self::add_to_counter(env, &AccountingKey::TotalDeposited(token.clone()), amount);
+        Self::add_to_counter(env, &AccountingKey::AssetLiability(payment_token.clone()), amount);
     }
 
-    /// Check if snapshot-based distribution is enabled for an offering.
-    pub fn get_snapshot_config(env: Env, issuer: Address, token: Address) -> bool {
-        let key = DataKey::SnapshotConfig(issuer, token);
-        env.storage().persistent().get(&key).unwrap_or(false)
+    /// Book a payout to a holder against the offering's liabilities.
+    fn record_payout(env: &Env, token: &Address, payment_token: &Address, amount: i128) {
+        Self::add_to_counter(env, &AccountingKey::TotalClaimed(token.clone()), amount);
+        Self::add_to_counter(env, &AccountingKey::AssetLiability(payment_token.clone()), -amount);
     }
 
-    /// Get the latest recorded snapshot reference for an offering.
-    pub fn get_last_snapshot_ref(env: Env, issuer: Address, token: Address) -> u64 {
-        let key = DataKey::LastSnapshotRef(issuer, token);
-        env.storage().persistent().get(&key).unwrap_or(0)
+    fn add_to_counter(env: &Env, key: &AccountingKey, delta: i128) {
+        let current: i128 = env.storage().persistent().get(key).unwrap_or(0);
+        env.storage().persistent().set(key, &(current + delta));
     }
 
-    /// Set a holder's revenue share (in basis points) for an offering.
+    // ── Merkle-root distributions ─────────────────────────────
+
+    /// Deposit revenue for a period and publish a Merkle root of its payouts.
     ///
-    /// The share determines the percentage of a period's revenue the holder can claim.
+    /// Instead of calling `set_holder_share` per holder, the issuer commits to a
+    /// Merkle root over `(leaf_index, holder, amount)` leaves and holders claim
+    /// with a proof via `claim_merkle`. Merkle periods are skipped by `claim`, so
+    /// both modes can coexist on the same offering.
     ///
-    /// ### Parameters
-    /// - `issuer`: The offering issuer. Must provide authentication.
-    /// - `token`: The token representing the offering.
-    /// - `holder`: The address of the token holder.
-    /// - `share_bps`: The holder's share in basis points (0-10000).
+    /// Leaf hash: `sha256(xdr((leaf_index, holder, amount)))`. Internal nodes hash
+    /// the two children in ascending byte order: `sha256(min || max)`.
     ///
     /// ### Returns
     /// - `Ok(())` on success.
-    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
-    /// - `Err(RevoraError::InvalidShareBps)` if `share_bps` exceeds 10000.
-    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
-    pub fn set_holder_share(
+    /// - Same errors as `deposit_revenue`.
+    pub fn deposit_revenue_with_merkle_root(
         env: Env,
         issuer: Address,
         token: Address,
-        holder: Address,
-        share_bps: u32,
+        payment_token: Address,
+        amount: i128,
+        period_id: u64,
+        merkle_root: BytesN<32>,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
 
@@ -1761,178 +5468,133 @@ impl RevoraRevenueShare {
 
         issuer.require_auth();
 
-        if share_bps > 10_000 {
-            return Err(RevoraError::InvalidShareBps);
-        }
+        // Stored first so the deposit is not accrued to accumulator-mode share holders
+        let root_key = DataKey::MerkleRoot(token.clone(), period_id);
+        env.storage().persistent().set(&root_key, &merkle_root);
 
-        let key = DataKey::HolderShare(token.clone(), holder.clone());
-        env.storage().persistent().set(&key, &share_bps);
+        Self::do_deposit_revenue(
+            &env,
+            issuer.clone(),
+            token.clone(),
+            payment_token,
+            amount,
+            period_id,
+        )?;
 
-        env.events().publish((EVENT_SHARE_SET, issuer, token), (holder, share_bps));
+        env.events().publish((EVENT_MERKLE_ROOT, issuer, token), (period_id, merkle_root));
         Ok(())
     }
 
-    /// Return a holder's share in basis points for an offering (0 if unset).
-    pub fn get_holder_share(env: Env, token: Address, holder: Address) -> u32 {
-        let key = DataKey::HolderShare(token, holder);
-        env.storage().persistent().get(&key).unwrap_or(0)
-    }
-
-    /// Claim aggregated revenue across multiple unclaimed periods.
-    ///
-    /// Payouts are calculated based on the holder's share at the time of claim.
-    /// Capped at `MAX_CLAIM_PERIODS` (50) per transaction for gas safety.
+    /// Claim a Merkle-mode period's payout by presenting a proof for the holder's leaf.
     ///
-    /// ### Parameters
-    /// - `holder`: The address of the token holder. Must provide authentication.
-    /// - `token`: The token representing the offering.
-    /// - `max_periods`: Maximum number of periods to process (0 = `MAX_CLAIM_PERIODS`).
+    /// Each `(period_id, leaf_index)` can be claimed once. The total paid out for a
+    /// period can never exceed its deposit, even if the published root is wrong.
     ///
     /// ### Returns
-    /// - `Ok(i128)` The total payout amount on success.
-    /// - `Err(RevoraError::HolderBlacklisted)` if the holder is blacklisted.
-    /// - `Err(RevoraError::NoPendingClaims)` if no share is set or all periods are claimed.
-    /// - `Err(RevoraError::ClaimDelayNotElapsed)` if the next period is still within the claim delay window.
-    pub fn claim(
+    /// - `Ok(i128)` The amount transferred.
+    /// - `Err(RevoraError::HolderSanctioned)`, `HolderBlacklisted` or `NotWhitelisted` if
+    ///   the holder is sanctioned, blacklisted or not whitelisted.
+    /// - `Err(RevoraError::InvalidAmount)` if `amount` is not positive or exceeds what remains.
+    /// - `Err(RevoraError::InvalidMerkleProof)` if no root is set or the proof does not verify.
+    /// - `Err(RevoraError::MerkleLeafAlreadyClaimed)` if the leaf was already claimed.
+    /// - `Err(RevoraError::ClaimDeadlinePassed)` if the period's claim deadline has passed.
+    /// - `Err(RevoraError::ClaimDelayNotElapsed)` if the period is still within the claim delay.
+    pub fn claim_merkle(
         env: Env,
         holder: Address,
         token: Address,
-        max_periods: u32,
+        period_id: u64,
+        leaf_index: u32,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
     ) -> Result<i128, RevoraError> {
         holder.require_auth();
 
-        if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
-            return Err(RevoraError::HolderBlacklisted);
-        }
-
-        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
-        if share_bps == 0 {
-            return Err(RevoraError::NoPendingClaims);
-        }
-
-        let count_key = DataKey::PeriodCount(token.clone());
-        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        Self::require_payout_eligible(&env, &token, &holder)?;
+        Self::require_positive_amount(amount)?;
 
-        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
-        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+        let root_key = DataKey::MerkleRoot(token.clone(), period_id);
+        let root: BytesN<32> =
+            env.storage().persistent().get(&root_key).ok_or(RevoraError::InvalidMerkleProof)?;
 
-        if start_idx >= period_count {
-            return Err(RevoraError::NoPendingClaims);
+        let claimed_key = DataKey::MerkleClaimed(token.clone(), period_id, leaf_index);
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(RevoraError::MerkleLeafAlreadyClaimed);
         }
 
-        let effective_max = if max_periods == 0 || max_periods > MAX_CLAIM_PERIODS {
-            MAX_CLAIM_PERIODS
-        } else {
-            max_periods
-        };
-        let end_idx = core::cmp::min(start_idx + effective_max, period_count);
-
-        let delay_key = DataKey::ClaimDelaySecs(token.clone());
+        let delay_key = DistributionKey::PeriodClaimDelay(token.clone(), period_id);
         let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
-        let now = env.ledger().timestamp();
-
-        let mut total_payout: i128 = 0;
-        let mut claimed_periods = Vec::new(&env);
-        let mut last_claimed_idx = start_idx;
-
-        for i in start_idx..end_idx {
-            let entry_key = DataKey::PeriodEntry(token.clone(), i);
-            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
-            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
-            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
-            if delay_secs > 0 && now < deposit_time.saturating_add(delay_secs) {
-                break;
-            }
-            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-            let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
-            let payout = revenue * (share_bps as i128) / 10_000;
-            total_payout += payout;
-            claimed_periods.push_back(period_id);
-            last_claimed_idx = i + 1;
+        let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+        let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+        if delay_secs > 0 && env.ledger().timestamp() < deposit_time.saturating_add(delay_secs) {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+        if Self::is_period_expired(&env, &token, period_id) {
+            return Err(RevoraError::ClaimDeadlinePassed);
         }
 
-        if last_claimed_idx == start_idx {
-            return Err(RevoraError::ClaimDelayNotElapsed);
+        let leaf = Self::merkle_leaf_hash(&env, leaf_index, &holder, amount);
+        if Self::merkle_compute_root(&env, leaf, &proof) != root {
+            return Err(RevoraError::InvalidMerkleProof);
         }
 
-        // Transfer only if there is a positive payout
-        if total_payout > 0 {
-            let pt_key = DataKey::PaymentToken(token.clone());
-            let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
-            let contract_addr = env.current_contract_address();
-            token::Client::new(&env, &payment_token).transfer(
-                &contract_addr,
-                &holder,
-                &total_payout,
-            );
+        // Never pay out more than was deposited for this period
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap_or(0);
+        let total_key = DataKey::MerkleClaimedTotal(token.clone(), period_id);
+        let claimed_total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        let new_total = claimed_total.checked_add(amount).ok_or(RevoraError::ArithmeticOverflow)?;
+        if new_total > revenue {
+            return Err(RevoraError::InvalidAmount);
         }
 
-        // Advance claim index only for periods actually claimed (respecting delay)
-        env.storage().persistent().set(&idx_key, &last_claimed_idx);
+        env.storage().persistent().set(&claimed_key, &true);
+        env.storage().persistent().set(&total_key, &new_total);
 
-        env.events().publish((EVENT_CLAIM, holder.clone(), token), (total_payout, claimed_periods));
+        let pt_key = DataKey::PaymentToken(token.clone());
+        let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+        token::Client::new(&env, &payment_token).transfer(
+            &env.current_contract_address(),
+            &holder,
+            &amount,
+        );
+        Self::record_payout(&env, &token, &payment_token, amount);
 
-        Ok(total_payout)
+        env.events().publish((EVENT_MERKLE_CLAIM, holder, token), (period_id, leaf_index, amount));
+        Ok(amount)
     }
 
-    /// Return unclaimed period IDs for a holder on an offering.
-    /// Ordering: by deposit index (creation order), deterministic (#38).
-    pub fn get_pending_periods(env: Env, token: Address, holder: Address) -> Vec<u64> {
-        let count_key = DataKey::PeriodCount(token.clone());
-        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder);
-        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
-
-        let mut periods = Vec::new(&env);
-        for i in start_idx..period_count {
-            let entry_key = DataKey::PeriodEntry(token.clone(), i);
-            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
-            periods.push_back(period_id);
-        }
-        periods
+    /// Return the Merkle root published for a period, if it is a Merkle-mode period.
+    pub fn get_merkle_root(env: Env, token: Address, period_id: u64) -> Option<BytesN<32>> {
+        let key = DataKey::MerkleRoot(token, period_id);
+        env.storage().persistent().get(&key)
     }
 
-    /// Preview the total claimable amount for a holder without mutating state.
-    ///
-    /// This method respects the per-offering claim delay and only sums periods that have passed the delay.
-    ///
-    /// ### Parameters
-    /// - `token`: The token representing the offering.
-    /// - `holder`: The address of the token holder.
-    ///
-    /// ### Returns
-    /// The total amount (i128) currently claimable by the holder.
-    pub fn get_claimable(env: Env, token: Address, holder: Address) -> i128 {
-        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
-        if share_bps == 0 {
-            return 0;
-        }
-
-        let count_key = DataKey::PeriodCount(token.clone());
-        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    /// Returns `true` if the Merkle leaf at `leaf_index` has been claimed for a period.
+    pub fn is_merkle_claimed(env: Env, token: Address, period_id: u64, leaf_index: u32) -> bool {
+        let key = DataKey::MerkleClaimed(token, period_id, leaf_index);
+        env.storage().persistent().has(&key)
+    }
 
-        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
-        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+    fn is_merkle_period(env: &Env, token: &Address, period_id: u64) -> bool {
+        env.storage().persistent().has(&DataKey::MerkleRoot(token.clone(), period_id))
+    }
 
-        let delay_key = DataKey::ClaimDelaySecs(token.clone());
-        let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
-        let now = env.ledger().timestamp();
+    fn merkle_leaf_hash(env: &Env, leaf_index: u32, holder: &Address, amount: i128) -> BytesN<32> {
+        let encoded = (leaf_index, holder.clone(), amount).to_xdr(env);
+        env.crypto().sha256(&encoded)
+    }
 
-        let mut total: i128 = 0;
-        for i in start_idx..period_count {
-            let entry_key = DataKey::PeriodEntry(token.clone(), i);
-            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
-            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
-            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
-            if delay_secs > 0 && now < deposit_time.saturating_add(delay_secs) {
-                break;
-            }
-            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-            let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
-            total += revenue * (share_bps as i128) / 10_000;
+    /// Fold `proof` into `leaf` using sorted-pair hashing and return the resulting root.
+    fn merkle_compute_root(env: &Env, leaf: BytesN<32>, proof: &Vec<BytesN<32>>) -> BytesN<32> {
+        let mut node = leaf;
+        for sibling in proof.iter() {
+            let (lo, hi) = if node <= sibling { (node, sibling) } else { (sibling, node) };
+            let mut buf = Bytes::from_array(env, &lo.to_array());
+            buf.append(&Bytes::from_array(env, &hi.to_array()));
+            node = env.crypto().sha256(&buf);
         }
-        total
+        node
     }
 
     // ── Time-delayed claim configuration (#27) ──────────────────
@@ -1940,6 +5602,8 @@ impl RevoraRevenueShare {
     /// Set the claim delay for an offering in seconds.
     ///
     /// The delay starts from the time of deposit and must elapse before a period can be claimed.
+    /// It is fixed per period when it is deposited; changing it only affects later deposits.
+    /// `claim` skips delayed periods and pays the ones after them that are claimable.
     ///
     /// ### Parameters
     /// - `issuer`: The offering issuer. Must provide authentication.
@@ -1949,6 +5613,7 @@ impl RevoraRevenueShare {
     /// ### Returns
     /// - `Ok(())` on success.
     /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found.
+    /// - `Err(RevoraError::DistributionModeConflict)` if the offering uses accumulator mode.
     /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
     pub fn set_claim_delay(
         env: Env,
@@ -1967,6 +5632,9 @@ impl RevoraRevenueShare {
         }
 
         issuer.require_auth();
+        if delay_secs > 0 && Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
         let key = DataKey::ClaimDelaySecs(token.clone());
         env.storage().persistent().set(&key, &delay_secs);
         env.events().publish((EVENT_CLAIM_DELAY_SET, issuer, token), delay_secs);
@@ -1979,6 +5647,66 @@ impl RevoraRevenueShare {
         env.storage().persistent().get(&key).unwrap_or(0)
     }
 
+    /// Set how each period's revenue is released after its deposit: nothing before
+    /// `cliff_secs`, then linearly over `linear_secs`. A cliff alone releases the
+    /// period at once; a linear window alone starts releasing at deposit.
+    ///
+    /// `claim` pays the released part of each period and can be called again as
+    /// more is released; the claim index stays on the first period still releasing.
+    /// The schedule is fixed per period at deposit; changing it only affects later
+    /// deposits. The cliff and any claim delay both run from the deposit, so a period
+    /// pays once the longer of the two has passed.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RevoraError::OfferingNotFound)` if the offering is not found or `issuer` is not current.
+    /// - `Err(RevoraError::DistributionModeConflict)` if the offering uses accumulator mode.
+    /// - `Err(RevoraError::ContractFrozen)` if the contract is frozen.
+    pub fn set_release_schedule(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        cliff_secs: u64,
+        linear_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        if Self::is_accumulator_mode(env.clone(), token.clone()) {
+            return Err(RevoraError::DistributionModeConflict);
+        }
+
+        let key = DistributionKey::ReleaseSchedule(token.clone());
+        env.storage().persistent().set(&key, &ReleaseSchedule { cliff_secs, linear_secs });
+        env.events().publish((EVENT_RELEASE_SET, issuer, token), (cliff_secs, linear_secs));
+        Ok(())
+    }
+
+    /// Get an offering's release schedule (all zero = released at deposit).
+    pub fn get_release_schedule(env: Env, token: Address) -> ReleaseSchedule {
+        let key = DistributionKey::ReleaseSchedule(token);
+        env.storage().persistent().get(&key).unwrap_or_default()
+    }
+
+    /// Return the release schedule a period was deposited under (all zero = released
+    /// at deposit).
+    pub fn get_period_release(env: Env, token: Address, period_id: u64) -> ReleaseSchedule {
+        let key = DistributionKey::PeriodRelease(token, period_id);
+        env.storage().persistent().get(&key).unwrap_or_default()
+    }
+
+    /// Return the amount a holder has already claimed from a period that is still releasing.
+    pub fn get_partial_claim(env: Env, token: Address, holder: Address, period_id: u64) -> i128 {
+        let key = DistributionKey::PartialClaim(token, holder, period_id);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
     /// Return the total number of deposited periods for an offering token.
     pub fn get_period_count(env: Env, token: Address) -> u32 {
         let count_key = DataKey::PeriodCount(token);
@@ -1990,29 +5718,142 @@ impl RevoraRevenueShare {
     /// Read-only: simulate distribution for sample inputs without mutating state.
     /// Returns expected payouts per holder and total. Uses offering's rounding mode.
     /// For integrators to preview outcomes before executing deposit/claim flows.
+    /// In `LargestRemainder` mode the result is that of `allocate_batch`; if the
+    /// shares are not a valid batch, payouts fall back to truncated shares.
     pub fn simulate_distribution(
         env: Env,
         issuer: Address,
         token: Address,
         amount: i128,
         holder_shares: Vec<(Address, u32)>,
+    ) -> SimulateDistributionResult {
+        let mode = Self::get_rounding_mode(env.clone(), issuer, token);
+        let shares = Self::bps_shares_as_units(&env, &holder_shares);
+        Self::simulate_in_units(&env, amount, &shares, BPS_DENOMINATOR, mode)
+    }
+
+    /// Like `simulate_distribution`, with shares in the offering's share units
+    /// (see `set_share_precision`).
+    pub fn simulate_distribution_units(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        amount: i128,
+        holder_shares: Vec<(Address, i128)>,
     ) -> SimulateDistributionResult {
         let mode = Self::get_rounding_mode(env.clone(), issuer, token.clone());
+        let scale = Self::share_scale(&env, &token);
+        Self::simulate_in_units(&env, amount, &holder_shares, scale, mode)
+    }
+
+    fn simulate_in_units(
+        env: &Env,
+        amount: i128,
+        holder_shares: &Vec<(Address, i128)>,
+        scale: i128,
+        mode: RoundingMode,
+    ) -> SimulateDistributionResult {
+        if mode == RoundingMode::LargestRemainder {
+            if let Ok(result) = Self::allocate_in_units(env, amount, holder_shares, scale) {
+                return result;
+            }
+        }
         let mut total: i128 = 0;
-        let mut payouts = Vec::new(&env);
-        for i in 0..holder_shares.len() {
-            let (holder, share_bps) = holder_shares.get(i).unwrap();
-            let payout = if share_bps > 10_000 {
-                0_i128
-            } else {
-                Self::compute_share(env.clone(), amount, share_bps, mode)
-            };
+        let mut payouts = Vec::new(env);
+        for (holder, share) in holder_shares.iter() {
+            let payout = Self::share_of(amount, share, scale, mode);
             total = total.saturating_add(payout);
-            payouts.push_back((holder.clone(), payout));
+            payouts.push_back((holder, payout));
         }
         SimulateDistributionResult { total_distributed: total, payouts }
     }
 
+    /// Split `amount` among `holder_shares` by largest remainder, with no dust.
+    ///
+    /// The distributable amount is `amount * sum(bps) / 10000`, truncated (all of
+    /// `amount` when the shares add up to 10000). Each holder first gets their truncated
+    /// share; the units left over go one each to the holders with the largest
+    /// remainders, ties broken by input order. Payouts sum exactly to the distributable amount.
+    ///
+    /// ### Returns
+    /// - `Ok(SimulateDistributionResult)` payouts in input order and their total.
+    /// - `Err(RevoraError::InvalidAmount)` if `amount` is negative.
+    /// - `Err(RevoraError::InvalidShareBps)` if a share or the sum of shares exceeds 10000.
+    pub fn allocate_batch(
+        env: Env,
+        amount: i128,
+        holder_shares: Vec<(Address, u32)>,
+    ) -> Result<SimulateDistributionResult, RevoraError> {
+        let shares = Self::bps_shares_as_units(&env, &holder_shares);
+        Self::allocate_in_units(&env, amount, &shares, BPS_DENOMINATOR)
+    }
+
+    /// Like `allocate_batch`, with shares in units of `precision`.
+    pub fn allocate_batch_units(
+        env: Env,
+        amount: i128,
+        holder_shares: Vec<(Address, i128)>,
+        precision: SharePrecision,
+    ) -> Result<SimulateDistributionResult, RevoraError> {
+        Self::allocate_in_units(&env, amount, &holder_shares, Self::precision_scale(precision))
+    }
+
+    fn allocate_in_units(
+        env: &Env,
+        amount: i128,
+        holder_shares: &Vec<(Address, i128)>,
+        scale: i128,
+    ) -> Result<SimulateDistributionResult, RevoraError> {
+        if amount < 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let mut total_share: i128 = 0;
+        for (_, share) in holder_shares.iter() {
+            if share < 0 {
+                return Err(RevoraError::InvalidShareBps);
+            }
+            total_share = total_share.saturating_add(share);
+        }
+        if total_share > scale {
+            return Err(RevoraError::InvalidShareBps);
+        }
+
+        let distributable =
+            Self::mul_div_rounded(amount, total_share, scale, RoundingMode::Truncation)?;
+        let mut payouts = Vec::new(env);
+        // Keyed by (scale - remainder, input index), so iteration runs from the largest
+        // remainder down, ties in input order
+        let mut by_remainder: Map<(i128, u32), ()> = Map::new(env);
+        let mut allocated: i128 = 0;
+        for (i, (holder, share)) in holder_shares.iter().enumerate() {
+            // Both fit in i128: the quotient is at most `amount`, the remainder below `scale`
+            let (payout, remainder) =
+                Self::mul_div_wide(amount as u128, share as u128, scale as u128)
+                    .ok_or(RevoraError::ArithmeticOverflow)?;
+            allocated += payout as i128;
+            payouts.push_back((holder, payout as i128));
+            by_remainder.set((scale - remainder as i128, i as u32), ());
+        }
+
+        // Fewer leftover units than holders: one each to the largest remainders
+        let leftover = distributable - allocated;
+        for (_, i) in by_remainder.keys().iter().take(leftover as usize) {
+            let (holder, payout) = payouts.get(i).unwrap();
+            payouts.set(i, (holder, payout + 1));
+        }
+
+        Ok(SimulateDistributionResult { total_distributed: distributable, payouts })
+    }
+
+    /// Widen bps shares to share units at `SharePrecision::Bps`.
+    fn bps_shares_as_units(env: &Env, holder_shares: &Vec<(Address, u32)>) -> Vec<(Address, i128)> {
+        let mut shares = Vec::new(env);
+        for (holder, share_bps) in holder_shares.iter() {
+            shares.push_back((holder, share_bps as i128));
+        }
+        shares
+    }
+
     // ── Upgradeability guard and freeze (#32) ───────────────────
 
     /// Set the admin address. May only be called once; caller must authorize as the new admin.
@@ -2434,6 +6275,10 @@ impl RevoraRevenueShare {
     ///
     /// Rounding: Uses integer division which rounds down (floor).
     /// This is conservative and ensures the contract never over-distributes.
+    /// The holder's fraction is `holder_balance / total_supply` itself, so it is never
+    /// quantized to the offering's share precision (bps or finer).
+    /// Both steps use a 256-bit intermediate; a result outside i128 returns
+    /// `Err(RevoraError::ArithmeticOverflow)`.
     // This entrypoint shape is part of the public contract interface and mirrors
     // off-chain inputs directly, so we allow this specific arity.
     #[allow(clippy::too_many_arguments)]
@@ -2446,7 +6291,7 @@ impl RevoraRevenueShare {
         total_supply: i128,
         holder_balance: i128,
         holder: Address,
-    ) -> i128 {
+    ) -> Result<i128, RevoraError> {
         caller.require_auth();
 
         if total_supply == 0 {
@@ -2464,58 +6309,56 @@ impl RevoraRevenueShare {
             let payout = 0i128;
             env.events().publish(
                 (EVENT_DIST_CALC, token.clone(), holder.clone()),
-                (
-                    total_revenue,
-                    total_supply,
-                    holder_balance,
-                    offering.revenue_share_bps,
-                    payout,
-                ),
+                (total_revenue, total_supply, holder_balance, offering.revenue_share_bps, payout),
             );
-            return payout;
+            return Ok(payout);
         }
 
-        let distributable_revenue = (total_revenue * offering.revenue_share_bps as i128)
-            .checked_div(BPS_DENOMINATOR)
-            .expect("division overflow");
+        let distributable_revenue = Self::mul_div_rounded(
+            total_revenue,
+            offering.revenue_share_bps as i128,
+            BPS_DENOMINATOR,
+            RoundingMode::Truncation,
+        )?;
 
-        let payout = (holder_balance * distributable_revenue)
-            .checked_div(total_supply)
-            .expect("division overflow");
+        let payout = Self::mul_div_rounded(
+            holder_balance,
+            distributable_revenue,
+            total_supply,
+            RoundingMode::Truncation,
+        )?;
 
         env.events().publish(
             (EVENT_DIST_CALC, token, holder),
-            (
-                total_revenue,
-                total_supply,
-                holder_balance,
-                offering.revenue_share_bps,
-                payout,
-            ),
+            (total_revenue, total_supply, holder_balance, offering.revenue_share_bps, payout),
         );
 
-        payout
+        Ok(payout)
     }
 
     /// Calculate the total distributable revenue for an offering.
     ///
     /// This is a helper function for off-chain verification.
+    /// Returns `Err(RevoraError::ArithmeticOverflow)` if the result does not fit in i128.
     pub fn calculate_total_distributable(
         env: Env,
         issuer: Address,
         token: Address,
         total_revenue: i128,
-    ) -> i128 {
+    ) -> Result<i128, RevoraError> {
         let offering =
             Self::get_offering(env, issuer, token).expect("offering not found for token");
 
         if total_revenue == 0 {
-            return 0;
+            return Ok(0);
         }
 
-        (total_revenue * offering.revenue_share_bps as i128)
-            .checked_div(BPS_DENOMINATOR)
-            .expect("division overflow")
+        Self::mul_div_rounded(
+            total_revenue,
+            offering.revenue_share_bps as i128,
+            BPS_DENOMINATOR,
+            RoundingMode::Truncation,
+        )
     }
 
     // ── Per-offering metadata storage (#8) ─────────────────────
@@ -2747,7 +6590,37 @@ impl RevoraRevenueShare {
     /// Calculate the platform fee for a given amount.
     pub fn calculate_platform_fee(env: Env, amount: i128) -> i128 {
         let fee_bps = Self::get_platform_fee(env) as i128;
-        (amount * fee_bps).checked_div(BPS_DENOMINATOR).unwrap_or(0)
+        // Cannot fail: the fee is capped at 100%, so it is at most |amount|
+        Self::mul_div_rounded(amount, fee_bps, BPS_DENOMINATOR, RoundingMode::Truncation)
+            .unwrap_or(0)
+    }
+
+    /// Return platform fees collected in `asset` and not yet withdrawn.
+    pub fn get_platform_fees_accrued(env: Env, asset: Address) -> i128 {
+        env.storage().persistent().get(&AccountingKey::PlatformFees(asset)).unwrap_or(0)
+    }
+
+    /// Withdraw all accrued platform fees in `asset` to `to`.  Admin-only.
+    /// Returns the amount transferred (0 if nothing has accrued).
+    pub fn withdraw_platform_fees(
+        env: Env,
+        asset: Address,
+        to: Address,
+    ) -> Result<i128, RevoraError> {
+        let admin = Self::get_admin(env.clone()).ok_or(RevoraError::NotInitialized)?;
+        admin.require_auth();
+
+        let amount = Self::get_platform_fees_accrued(env.clone(), asset.clone());
+        if amount > 0 {
+            env.storage().persistent().remove(&AccountingKey::PlatformFees(asset.clone()));
+            token::Client::new(&env, &asset).transfer(
+                &env.current_contract_address(),
+                &to,
+                &amount,
+            );
+        }
+        env.events().publish((EVENT_FEE_WITHDRAWN, admin, asset), (to, amount));
+        Ok(amount)
     }
 
     /// Return the current contract version (#23). Used for upgrade compatibility and migration.
@@ -1,16 +1,17 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::{Address as _, Events}, Address, Env};
-use crate::{RevoraRevenueShare, RevoraRevenueShareClient};
-use crate::{RevoraError, RevoraRevenueShare, RevoraRevenueShareClient, RoundingMode};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events as _, Ledger as _},
-    token, vec, Address, Env, IntoVal, String as SdkString, Symbol, Vec,
+    token, vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, String as SdkString, Symbol, Vec,
 };
 
 use crate::{
-    ProposalAction, RevoraError, RevoraRevenueShare, RevoraRevenueShareClient, RoundingMode,
+    BlacklistPolicy, BlacklistRecord, ClaimResult, ListDigest, PendingPeriod, ProposalAction,
+    ReleaseSchedule, RevoraError, RevoraRevenueShare, RevoraRevenueShareClient, RoundingMode,
+    ShareCheckpoint, ShareCheckpointUnits, SharePrecision,
 };
 
 // ── helper ────────────────────────────────────────────────────
@@ -23,6 +24,7 @@ fn make_client(env: &Env) -> RevoraRevenueShareClient<'_> {
 const BOUNDARY_AMOUNTS: [i128; 7] = [i128::MIN, i128::MIN + 1, -1, 0, 1, i128::MAX - 1, i128::MAX];
 const BOUNDARY_PERIODS: [u64; 6] = [0, 1, 2, 10_000, u64::MAX - 1, u64::MAX];
 const FUZZ_ITERATIONS: usize = 128;
+const STORAGE_STRESS_OFFERING_COUNT: u32 = 100;
 
 fn next_u64(seed: &mut u64) -> u64 {
 
@@ -57,7 +59,7 @@ fn next_period(seed: &mut u64) -> u64 {
 //    topic[0] = Symbol("rev_rep")
 //    topic[1] = Address  (issuer)
 //    topic[2] = Address  (token)
-//    data     = (i128 (amount), u64 (period_id), Vec<Address> (blacklist))
+//    data     = (i128 (amount), u64 (period_id), ListDigest (blacklist))
 //
 // ─────────────────────────────────────────────────────────────────────────────
 
@@ -106,7 +108,7 @@ fn report_revenue_emits_exact_event() {
     client.register_offering(&issuer, &token, &1000, &token);
     client.report_revenue(&issuer, &token, &token, &amount, &period_id, &false);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         env.events().all(),
         vec![
@@ -174,7 +176,7 @@ fn combined_flow_preserves_event_order() {
     let events = env.events().all();
     assert_eq!(events.len(), 5);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         events,
         vec![
@@ -242,7 +244,7 @@ fn complex_mixed_flow_events_in_order() {
     let events = env.events().all();
     assert_eq!(events.len(), 10);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         events,
         vec![
@@ -389,7 +391,7 @@ fn multiple_revenue_reports_same_offering() {
     let events = env.events().all();
     assert_eq!(events.len(), 13);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         events,
         vec![
@@ -519,7 +521,7 @@ fn same_issuer_different_tokens() {
     let events = env.events().all();
     assert_eq!(events.len(), 10);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         events,
         vec![
@@ -618,7 +620,7 @@ fn topic_symbols_are_distinct() {
     client.register_offering(&issuer, &token, &1_000, &token);
     client.report_revenue(&issuer, &token, &token, &1_000_000, &1, &false);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         env.events().all(),
         vec![
@@ -678,7 +680,7 @@ fn rev_rep_topics_include_token_address() {
     client.register_offering(&issuer, &token, &1000, &token);
     client.report_revenue(&issuer, &token, &token, &999, &7, &false);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         env.events().all(),
         vec![
@@ -793,7 +795,7 @@ fn zero_amount_revenue_report() {
     client.register_offering(&issuer, &token, &1000, &token);
     client.report_revenue(&issuer, &token, &token, &0, &1, &false);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         env.events().all(),
         vec![
@@ -854,7 +856,7 @@ fn large_revenue_amount() {
     client.register_offering(&issuer, &token, &1000, &token);
     client.report_revenue(&issuer, &token, &token, &large_amount, &u64::MAX, &false);
 
-    let empty_bl = Vec::<Address>::new(&env);
+    let empty_bl = ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) };
     assert_eq!(
         env.events().all(),
         vec![
@@ -991,15 +993,6 @@ fn fuzz_period_and_amount_boundaries_do_not_panic() {
         }
     }
 
-
-    // Each report_revenue call emits 2 events: a specific event (rev_init/rev_ovrd/rev_rej)
-    // plus the backward-compatible rev_rep event.
-    assert_eq!(env.events().all().len(), (calls * 2) as u32);
-
-    assert_eq!(env.events().all().len(), (calls as u32) * 2);
-
-    assert_eq!(env.events().all().len(), 1 + (calls as u32) * 4);
-
     assert!(accepted > 0);
 }
 
@@ -1043,14 +1036,6 @@ fn fuzz_period_and_amount_repeatable_sweep_do_not_panic() {
         }
     }
 
-
-    // Each report_revenue call emits 2 events (specific + backward-compatible rev_rep).
-    assert_eq!(env.events().all().len(), (FUZZ_ITERATIONS * 2) as u32);
-
-    assert_eq!(env.events().all().len(), (FUZZ_ITERATIONS as u32) * 2);
-
-    assert_eq!(env.events().all().len(), 1 + (FUZZ_ITERATIONS as u32) * 4);
-
     assert!(accepted > 0);
 }
 
@@ -1277,12 +1262,13 @@ fn add_marks_investor_as_blacklisted() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
     assert!(!client.is_blacklisted(&token, &investor));
-    client.blacklist_add(&admin, &token, &investor);
+    client.blacklist_add(&issuer, &token, &investor);
     assert!(client.is_blacklisted(&token, &investor));
 }
 
@@ -1291,12 +1277,13 @@ fn remove_unmarks_investor() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
-    client.blacklist_add(&admin, &token, &investor);
-    client.blacklist_remove(&admin, &token, &investor);
+    client.blacklist_add(&issuer, &token, &investor);
+    client.blacklist_remove(&issuer, &token, &investor);
     assert!(!client.is_blacklisted(&token, &investor));
 }
 
@@ -1305,21 +1292,23 @@ fn get_blacklist_returns_all_blocked_investors() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let inv_a = Address::generate(&env);
     let inv_b = Address::generate(&env);
     let inv_c = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
-    client.blacklist_add(&admin, &token, &inv_a);
-    client.blacklist_add(&admin, &token, &inv_b);
-    client.blacklist_add(&admin, &token, &inv_c);
+    client.blacklist_add(&issuer, &token, &inv_a);
+    client.blacklist_add(&issuer, &token, &inv_b);
+    client.blacklist_add(&issuer, &token, &inv_c);
 
-    let list = client.get_blacklist(&token);
+    let (list, next) = client.get_blacklist(&token, &0, &0);
     assert_eq!(list.len(), 3);
-    assert!(list.contains(&inv_a));
-    assert!(list.contains(&inv_b));
-    assert!(list.contains(&inv_c));
+    assert_eq!(next, None);
+    assert!(list.iter().any(|r| r.investor == inv_a));
+    assert!(list.iter().any(|r| r.investor == inv_b));
+    assert!(list.iter().any(|r| r.investor == inv_c));
 }
 
 #[test]
@@ -1329,7 +1318,7 @@ fn get_blacklist_empty_before_any_add() {
     let client = make_client(&env);
     let token = Address::generate(&env);
 
-    assert_eq!(client.get_blacklist(&token).len(), 0);
+    assert_eq!(client.get_blacklist(&token, &0, &0).0.len(), 0);
 }
 
 // ── idempotency ───────────────────────────────────────────────
@@ -1339,14 +1328,15 @@ fn double_add_is_idempotent() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
-    client.blacklist_add(&admin, &token, &investor);
-    client.blacklist_add(&admin, &token, &investor);
+    client.blacklist_add(&issuer, &token, &investor);
+    client.blacklist_add(&issuer, &token, &investor);
 
-    assert_eq!(client.get_blacklist(&token).len(), 1);
+    assert_eq!(client.get_blacklist(&token, &0, &0).0.len(), 1);
 }
 
 #[test]
@@ -1354,11 +1344,12 @@ fn remove_nonexistent_is_idempotent() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
-    client.blacklist_remove(&admin, &token, &investor); // must not panic
+    client.blacklist_remove(&issuer, &token, &investor); // must not panic
     assert!(!client.is_blacklisted(&token, &investor));
 }
 
@@ -1369,12 +1360,14 @@ fn blacklist_is_scoped_per_offering() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token_a = Address::generate(&env);
     let token_b = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token_a, &1_000, &token_a);
+    client.register_offering(&issuer, &token_b, &1_000, &token_b);
 
-    client.blacklist_add(&admin, &token_a, &investor);
+    client.blacklist_add(&issuer, &token_a, &investor);
 
     assert!(client.is_blacklisted(&token_a, &investor));
     assert!(!client.is_blacklisted(&token_b, &investor));
@@ -1385,14 +1378,16 @@ fn removing_from_one_offering_does_not_affect_another() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token_a = Address::generate(&env);
     let token_b = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token_a, &1_000, &token_a);
+    client.register_offering(&issuer, &token_b, &1_000, &token_b);
 
-    client.blacklist_add(&admin, &token_a, &investor);
-    client.blacklist_add(&admin, &token_b, &investor);
-    client.blacklist_remove(&admin, &token_a, &investor);
+    client.blacklist_add(&issuer, &token_a, &investor);
+    client.blacklist_add(&issuer, &token_b, &investor);
+    client.blacklist_remove(&issuer, &token_a, &investor);
 
     assert!(!client.is_blacklisted(&token_a, &investor));
     assert!(client.is_blacklisted(&token_b, &investor));
@@ -1405,12 +1400,13 @@ fn blacklist_add_emits_event() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
     let before = env.events().all().len();
-    client.blacklist_add(&admin, &token, &investor);
+    client.blacklist_add(&issuer, &token, &investor);
     assert!(env.events().all().len() > before);
 }
 
@@ -1419,13 +1415,14 @@ fn blacklist_remove_emits_event() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
-    client.blacklist_add(&admin, &token, &investor);
+    client.blacklist_add(&issuer, &token, &investor);
     let before = env.events().all().len();
-    client.blacklist_remove(&admin, &token, &investor);
+    client.blacklist_remove(&issuer, &token, &investor);
     assert!(env.events().all().len() > before);
 }
 
@@ -1436,12 +1433,13 @@ fn blacklisted_investor_excluded_from_distribution_filter() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let allowed = Address::generate(&env);
     let blocked = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
-    client.blacklist_add(&admin, &token, &blocked);
+    client.blacklist_add(&issuer, &token, &blocked);
 
     let investors = [allowed.clone(), blocked.clone()];
     let eligible = investors.iter().filter(|inv| !client.is_blacklisted(&token, inv)).count();
@@ -1454,11 +1452,12 @@ fn blacklist_takes_precedence_over_whitelist() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
-    client.blacklist_add(&admin, &token, &investor);
+    client.blacklist_add(&issuer, &token, &investor);
 
     // Even if investor were on a whitelist, blacklist must win
     assert!(client.is_blacklisted(&token, &investor));
@@ -1492,6 +1491,11 @@ fn blacklist_remove_requires_auth() {
 
 // ── whitelist CRUD ────────────────────────────────────────────
 
+/// Register `token` as an offering of a fresh issuer; list changes need one.
+fn register_offering_for(env: &Env, client: &RevoraRevenueShareClient, token: &Address) {
+    client.register_offering(&Address::generate(env), token, &1_000, token);
+}
+
 #[test]
 fn whitelist_add_marks_investor_as_whitelisted() {
     let env = Env::default();
@@ -1500,13 +1504,9 @@ fn whitelist_add_marks_investor_as_whitelisted() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
-    let result = client.try_register_offering(&issuer, &token, &10_001, &payout_asset);
-    assert!(
-        result.is_err(),
-        "contract must return Err(RevoraError::InvalidRevenueShareBps) for bps > 10000"
-    );
-    assert_eq!(RevoraError::InvalidRevenueShareBps as u32, 1, "error code for integrators");
     assert!(!client.is_whitelisted(&token, &investor));
     client.whitelist_add(&admin, &token, &investor);
     assert!(client.is_whitelisted(&token, &investor));
@@ -1520,6 +1520,8 @@ fn whitelist_remove_unmarks_investor() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     client.whitelist_add(&admin, &token, &investor);
     client.whitelist_remove(&admin, &token, &investor);
@@ -1536,12 +1538,14 @@ fn get_whitelist_returns_all_approved_investors() {
     let inv_a  = Address::generate(&env);
     let inv_b  = Address::generate(&env);
     let inv_c  = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     client.whitelist_add(&admin, &token, &inv_a);
     client.whitelist_add(&admin, &token, &inv_b);
     client.whitelist_add(&admin, &token, &inv_c);
 
-    let list = client.get_whitelist(&token);
+    let (list, _) = client.get_whitelist_page(&token, &0, &0);
     assert_eq!(list.len(), 3);
     assert!(list.contains(&inv_a));
     assert!(list.contains(&inv_b));
@@ -1555,22 +1559,7 @@ fn get_whitelist_empty_before_any_add() {
     let client = make_client(&env);
     let token  = Address::generate(&env);
 
-    for period_id in 1..=100_u64 {
-
-        client.report_revenue(&issuer, &token, &(period_id as i128 * 10_000), &period_id, &false);
-
-        client.report_revenue(
-            &issuer,
-            &token,
-            &payout_asset,
-            &(period_id as i128 * 10_000),
-            &period_id,
-            &false,
-        );
-
-    }
-    assert!(env.events().all().len() >= 100);
-    assert_eq!(client.get_whitelist(&token).len(), 0);
+    assert_eq!(client.get_whitelist_page(&token, &0, &0).0.len(), 0);
 }
 
 // ── whitelist idempotency ─────────────────────────────────────
@@ -1583,11 +1572,13 @@ fn whitelist_double_add_is_idempotent() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     client.whitelist_add(&admin, &token, &investor);
     client.whitelist_add(&admin, &token, &investor);
 
-    assert_eq!(client.get_whitelist(&token).len(), 1);
+    assert_eq!(client.get_whitelist_page(&token, &0, &0).0.len(), 1);
 }
 
 #[test]
@@ -1598,6 +1589,8 @@ fn whitelist_remove_nonexistent_is_idempotent() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     client.whitelist_remove(&admin, &token, &investor); // must not panic
     assert!(!client.is_whitelisted(&token, &investor));
@@ -1614,6 +1607,9 @@ fn whitelist_is_scoped_per_offering() {
     let token_a  = Address::generate(&env);
     let token_b  = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token_a);
+    register_offering_for(&env, &client, &token_b);
 
     client.whitelist_add(&admin, &token_a, &investor);
 
@@ -1630,6 +1626,9 @@ fn whitelist_removing_from_one_offering_does_not_affect_another() {
     let token_a  = Address::generate(&env);
     let token_b  = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token_a);
+    register_offering_for(&env, &client, &token_b);
 
     client.whitelist_add(&admin, &token_a, &investor);
     client.whitelist_add(&admin, &token_b, &investor);
@@ -1649,6 +1648,8 @@ fn whitelist_add_emits_event() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     let before = env.events().all().len();
     client.whitelist_add(&admin, &token, &investor);
@@ -1663,6 +1664,8 @@ fn whitelist_remove_emits_event() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     client.whitelist_add(&admin, &token, &investor);
     let before = env.events().all().len();
@@ -1681,6 +1684,8 @@ fn whitelist_enabled_only_includes_whitelisted_investors() {
     let token      = Address::generate(&env);
     let whitelisted = Address::generate(&env);
     let not_listed  = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     client.whitelist_add(&admin, &token, &whitelisted);
 
@@ -1748,6 +1753,8 @@ fn blacklist_overrides_whitelist() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     // Add to both whitelist and blacklist
     client.whitelist_add(&admin, &token, &investor);
@@ -1806,6 +1813,8 @@ fn large_whitelist_operations() {
     let client = make_client(&env);
     let admin  = Address::generate(&env);
     let token  = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     // Add 50 investors to whitelist
     let mut investors = soroban_sdk::Vec::new(&env);
@@ -1815,8 +1824,7 @@ fn large_whitelist_operations() {
         investors.push_back(inv);
     }
 
-    let whitelist = client.get_whitelist(&token);
-    assert_eq!(whitelist.len(), 50);
+    assert_eq!(client.get_whitelist_digest(&token).count, 50);
 
     // Verify all are whitelisted
     for i in 0..investors.len() {
@@ -1834,6 +1842,8 @@ fn repeated_whitelist_operations_on_same_address() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     // Add, remove, add again
     client.whitelist_add(&admin, &token, &investor);
@@ -1856,6 +1866,8 @@ fn whitelist_enabled_when_non_empty() {
     let admin    = Address::generate(&env);
     let token    = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    register_offering_for(&env, &client, &token);
 
     assert!(!client.is_whitelist_enabled(&token));
     
@@ -1864,6 +1876,8 @@ fn whitelist_enabled_when_non_empty() {
     
     client.whitelist_remove(&admin, &token, &investor);
     assert!(!client.is_whitelist_enabled(&token));
+}
+
 // ── structured error codes (#41) ──────────────────────────────
 
 #[test]
@@ -1902,16 +1916,26 @@ fn register_offering_accepts_bps_exactly_10000() {
 
 // ── revenue index ─────────────────────────────────────────────
 
+fn revenue_index_setup(env: &Env) -> (RevoraRevenueShareClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    let client = make_client(env);
+    let issuer = Address::generate(env);
+    let token = Address::generate(env);
+    let payout_asset = Address::generate(env);
+    client.register_offering(&issuer, &token, &1_000, &payout_asset);
+    (client, issuer, token, payout_asset)
+}
+
 #[test]
 fn single_report_is_persisted() {
     let env = Env::default();
-    env.mock_all_auths();
-    let client = make_client(&env);
-    let issuer = Address::generate(&env);
-    let token = Address::generate(&env);
+    let (client, issuer, token, payout_asset) = revenue_index_setup(&env);
 
-    client.report_revenue(&issuer, &token, &5_000, &1, &false);
+    client.report_revenue(&issuer, &token, &payout_asset, &5_000, &1, &false);
     assert_eq!(client.get_revenue_by_period(&token, &1), 5_000);
+}
+
+#[test]
 fn storage_stress_many_offerings_no_panic() {
     let (env, client, issuer) = setup();
     register_n(&env, &client, &issuer, STORAGE_STRESS_OFFERING_COUNT);
@@ -1924,46 +1948,19 @@ fn storage_stress_many_offerings_no_panic() {
 }
 
 #[test]
-fn multiple_reports_same_period_accumulate() {
+fn multiple_reports_same_period_override_replaces_amount() {
     let env = Env::default();
-    env.mock_all_auths();
-    let client = make_client(&env);
-    let issuer = Address::generate(&env);
-    let token = Address::generate(&env);
+    let (client, issuer, token, payout_asset) = revenue_index_setup(&env);
+
+    client.report_revenue(&issuer, &token, &payout_asset, &3_000, &7, &false);
+    client.report_revenue(&issuer, &token, &payout_asset, &2_000, &7, &true);
+    assert_eq!(client.get_revenue_by_period(&token, &7), 2_000);
+}
 
-    client.report_revenue(&issuer, &token, &3_000, &7, &false);
-    client.report_revenue(&issuer, &token, &2_000, &7, &true); // Use true for override to test accumulation if intended, but wait...
-                                                               // Actually, report_revenue in lib.rs now OVERWRITES if override_existing is true.
-                                                               // beda819 wanted accumulation.
-                                                               // If I want accumulation, I should change lib.rs to accumulate even on override?
-                                                               // Let's re-read lib.rs implementation I just made.
-                                                               /*
-                                                               if override_existing {
-                                                                   cumulative_revenue = cumulative_revenue.checked_sub(existing_amount)...checked_add(amount)...
-                                                                   reports.set(period_id, (amount, current_timestamp));
-                                                               }
-                                                               */
-    // That overwrites.
-    // If I want to support beda819's "accumulation", I should perhaps NOT use override_existing for accumulation.
-    // But the tests in beda819 were:
-    /*
-    client.report_revenue(&issuer, &token, &3_000, &7);
-    client.report_revenue(&issuer, &token, &2_000, &7);
-    assert_eq!(client.get_revenue_by_period(&token, &7), 5_000);
-    */
-    // This implies that multiple reports for the same period SHOULD accumulate.
-    // My lib.rs implementation rejects if it exists and override_existing is false.
-    // I should change lib.rs to ACCUMULATE by default or if a special flag is set.
-    // Or I can just fix the tests to match the new behavior (one report per period).
-    // Given "Revora" context, usually a "report" is a single statement for a period.
-    // Fix tests to match one-report-per-period with override logic.
+#[test]
+fn many_periods_report_without_panic() {
     let env = Env::default();
-    env.mock_all_auths();
-    let client = make_client(&env);
-    let issuer = Address::generate(&env);
-    let token = Address::generate(&env);
-    let payout_asset = Address::generate(&env);
-    client.register_offering(&issuer, &token, &1_000, &payout_asset);
+    let (client, issuer, token, payout_asset) = revenue_index_setup(&env);
 
     for period_id in 1..=100_u64 {
         client.report_revenue(
@@ -1981,15 +1978,11 @@ fn multiple_reports_same_period_accumulate() {
 #[test]
 fn multiple_reports_same_period_accumulate_is_disabled() {
     let env = Env::default();
-    env.mock_all_auths();
-    let client = make_client(&env);
-    let issuer = Address::generate(&env);
-    let token = Address::generate(&env);
+    let (client, issuer, token, payout_asset) = revenue_index_setup(&env);
 
-    client.report_revenue(&issuer, &token, &3_000, &7, &false);
-    // Second report without override should fail or just emit REJECTED event depending on implementation.
-    // In my lib.rs it emits REJECTED and returns Ok(()).
-    client.report_revenue(&issuer, &token, &2_000, &7, &false);
+    client.report_revenue(&issuer, &token, &payout_asset, &3_000, &7, &false);
+    // A second report without override is rejected (rev_rej) and leaves the index untouched.
+    client.report_revenue(&issuer, &token, &payout_asset, &2_000, &7, &false);
     assert_eq!(client.get_revenue_by_period(&token, &7), 3_000);
 }
 
@@ -2004,7 +1997,6 @@ fn empty_period_returns_zero() {
 }
 
 #[test]
-fn get_revenue_range_sums_periods() {
 fn gas_characterization_many_offerings_single_issuer() {
     let (env, client, issuer) = setup();
     let n = 50_u32;
@@ -2017,19 +2009,11 @@ fn gas_characterization_many_offerings_single_issuer() {
 #[test]
 fn gas_characterization_report_revenue_with_large_blacklist() {
     let env = Env::default();
-    env.mock_all_auths();
-    let client = make_client(&env);
-    let issuer = Address::generate(&env);
-    let token = Address::generate(&env);
-    let payout_asset = Address::generate(&env);
-    client.register_offering(&issuer, &token, &500, &payout_asset);
+    let (client, issuer, token, payout_asset) = revenue_index_setup(&env);
 
     for _ in 0..30 {
-        client.blacklist_add(&Address::generate(&env), &token, &Address::generate(&env));
+        client.blacklist_add(&issuer, &token, &Address::generate(&env));
     }
-    let admin = Address::generate(&env);
-    env.mock_all_auths();
-    client.blacklist_add(&admin, &token, &Address::generate(&env));
 
     client.report_revenue(&issuer, &token, &payout_asset, &1_000_000, &1, &false);
     assert!(!env.events().all().is_empty());
@@ -2038,13 +2022,10 @@ fn gas_characterization_report_revenue_with_large_blacklist() {
 #[test]
 fn revenue_matches_event_amount() {
     let env = Env::default();
-    env.mock_all_auths();
-    let client = make_client(&env);
-    let issuer = Address::generate(&env);
-    let token = Address::generate(&env);
+    let (client, issuer, token, payout_asset) = revenue_index_setup(&env);
     let amount: i128 = 42_000;
 
-    client.report_revenue(&issuer, &token, &amount, &5, &false);
+    client.report_revenue(&issuer, &token, &payout_asset, &amount, &5, &false);
 
     assert_eq!(client.get_revenue_by_period(&token, &5), amount);
     assert!(!env.events().all().is_empty());
@@ -2053,12 +2034,12 @@ fn revenue_matches_event_amount() {
 #[test]
 fn large_period_range_sums_correctly() {
     let env = Env::default();
-    env.mock_all_auths();
-    let client = make_client(&env);
-    let issuer = Address::generate(&env);
-    let token = Address::generate(&env);
-    client.register_offering(&issuer, &token, &1_000);
-    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    let (client, issuer, token, payout_asset) = revenue_index_setup(&env);
+
+    for period_id in 1..=10_u64 {
+        client.report_revenue(&issuer, &token, &payout_asset, &1_000, &period_id, &false);
+    }
+    assert_eq!(client.get_revenue_range(&token, &1, &10), 10_000);
 }
 
 // ---------------------------------------------------------------------------
@@ -2180,12 +2161,7 @@ fn concentration_near_threshold_boundary() {
     client.set_concentration_limit(&issuer, &token, &5000, &true);
     client.report_concentration(&issuer, &token, &5001);
 
-    assert!(client.try_report_revenue(&issuer, &token, &1_000, &1, &false).is_err());
-
-    assert!(client
-        .try_report_revenue(&issuer, &token, &payout_asset, &1_000, &1, &false)
-        .is_err());
-
+    assert!(client.try_report_revenue(&issuer, &token, &payout_asset, &1_000, &1, &false).is_err());
 }
 
 // ---------------------------------------------------------------------------
@@ -2294,9 +2270,6 @@ fn set_and_get_rounding_mode() {
     let issuer = Address::generate(&env);
     let token = Address::generate(&env);
 
-    client.register_offering(&issuer, &token, &1_000);
-    assert_eq!(client.get_rounding_mode(&issuer, &token), RoundingMode::Truncation);
-
     let payout_asset = Address::generate(&env);
     client.register_offering(&issuer, &token, &1_000, &payout_asset);
     assert_eq!(
@@ -2386,6 +2359,27 @@ fn claim_setup() -> (Env, RevoraRevenueShareClient<'static>, Address, Address, A
     (env, client, issuer, token, payment_token, contract_id)
 }
 
+/// Like `claim_setup`, with the offering registered at `revenue_share_bps`.
+fn claim_setup_with_share(
+    revenue_share_bps: u32,
+) -> (Env, RevoraRevenueShareClient<'static>, Address, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RevoraRevenueShare);
+    let client = RevoraRevenueShareClient::new(&env, &contract_id);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (payment_token, pt_admin) = create_payment_token(&env);
+
+    // Register offering
+    client.register_offering(&issuer, &token, &revenue_share_bps, &payment_token);
+
+    // Mint payment tokens to the issuer so they can deposit
+    mint_tokens(&env, &payment_token, &pt_admin, &issuer, &10_000_000);
+
+    (env, client, issuer, token, payment_token, contract_id)
+}
+
 // ── deposit_revenue tests ─────────────────────────────────────
 
 #[test]
@@ -2567,7 +2561,7 @@ fn set_holder_share_fails_for_bps_over_10000() {
 
 #[test]
 fn set_holder_share_accepts_bps_exactly_10000() {
-    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     let result = client.try_set_holder_share(&issuer, &token, &holder, &10_000);
@@ -2626,7 +2620,7 @@ fn claim_multiple_periods_aggregated() {
 
 #[test]
 fn claim_max_periods_zero_claims_all() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -2640,7 +2634,7 @@ fn claim_max_periods_zero_claims_all() {
 
 #[test]
 fn claim_partial_then_rest() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -2661,7 +2655,7 @@ fn claim_partial_then_rest() {
 
 #[test]
 fn claim_no_double_counting() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -2748,7 +2742,7 @@ fn claim_fails_for_zero_share_holder() {
 
 #[test]
 fn claim_sparse_period_ids() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -2786,7 +2780,7 @@ fn claim_multiple_holders_same_periods() {
 
 #[test]
 fn claim_with_max_periods_cap() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -2810,7 +2804,7 @@ fn claim_with_max_periods_cap() {
 
 #[test]
 fn claim_zero_revenue_periods_still_advance() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -2915,7 +2909,7 @@ fn get_claimable_returns_correct_amount() {
 
 #[test]
 fn get_claimable_after_partial_claim() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -2938,9 +2932,30 @@ fn get_claimable_returns_zero_for_unknown_holder() {
     assert_eq!(client.get_claimable(&token, &unknown), 0);
 }
 
+#[test]
+fn get_claimable_returns_zero_when_the_total_overflows() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1, &2);
+    env.as_contract(&contract_id, || {
+        for period_id in [1_u64, 2] {
+            let key = crate::DataKey::PeriodRevenue(token.clone(), period_id);
+            env.storage().persistent().set(&key, &i128::MAX);
+        }
+    });
+
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    let r = client.try_get_claimable_checked(&token, &holder);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ArithmeticOverflow)));
+    let r = client.try_claim(&holder, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ArithmeticOverflow)));
+}
+
 #[test]
 fn get_claimable_returns_zero_after_full_claim() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000);
@@ -2961,7 +2976,7 @@ fn get_period_count_default_zero() {
 
 #[test]
 fn multiple_holders_independent_claim_indices() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder_a = Address::generate(&env);
     let holder_b = Address::generate(&env);
 
@@ -3024,6 +3039,8 @@ fn claim_many_periods_stress() {
     for i in 1..=50_u64 {
         client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &i);
     }
+    // Measure the claim alone, not the 50 deposits before it
+    env.budget().reset_default();
 
     // Claim all 50 in one transaction
     let payout = client.claim(&holder, &token, &0);
@@ -3038,7 +3055,7 @@ fn claim_many_periods_stress() {
 
 #[test]
 fn claim_exceeding_max_is_capped() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -3047,6 +3064,8 @@ fn claim_exceeding_max_is_capped() {
     for i in 1..=55_u64 {
         client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &i);
     }
+    // Measure the claims alone, not the 55 deposits before them
+    env.budget().reset_default();
 
     // Request 100 periods - should be capped at 50
     let payout1 = client.claim(&holder, &token, &100);
@@ -3097,7 +3116,7 @@ fn claim_with_rounding() {
 
 #[test]
 fn claim_single_unit_revenue() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
@@ -3109,7 +3128,7 @@ fn claim_single_unit_revenue() {
 
 #[test]
 fn deposit_then_claim_then_deposit_then_claim() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
     client.set_holder_share(&issuer, &token, &holder, &10_000); // 100%
 
@@ -3134,7 +3153,7 @@ fn offering_isolation_claims_independent() {
     // Register a second offering
     let token_b = Address::generate(&env);
     let (pt_b, pt_b_admin) = create_payment_token(&env);
-    client.register_offering(&issuer, &token_b, &3_000, &pt_b);
+    client.register_offering(&issuer, &token_b, &10_000, &pt_b);
 
     // Create a second payment token for offering B
     mint_tokens(&env, &pt_b, &pt_b_admin, &issuer, &5_000_000);
@@ -3182,7 +3201,7 @@ fn set_claim_delay_requires_offering() {
 
 #[test]
 fn claim_before_delay_returns_claim_delay_not_elapsed() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     env.ledger().with_mut(|li| li.timestamp = 1000);
@@ -3196,7 +3215,7 @@ fn claim_before_delay_returns_claim_delay_not_elapsed() {
 
 #[test]
 fn claim_after_delay_succeeds() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     env.ledger().with_mut(|li| li.timestamp = 1000);
@@ -3226,7 +3245,7 @@ fn get_claimable_respects_delay() {
 
 #[test]
 fn claim_delay_partial_periods_only_claimable_after_delay() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
 
     env.ledger().with_mut(|li| li.timestamp = 1000);
@@ -3245,6 +3264,33 @@ fn claim_delay_partial_periods_only_claimable_after_delay() {
     assert_eq!(payout2, 200_000);
 }
 
+#[test]
+fn claim_skips_a_delayed_period_and_pays_the_claimable_one_after_it() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.set_claim_delay(&issuer, &token, &500);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    // The delay is fixed at deposit: lifting it does not release period 1 early
+    client.set_claim_delay(&issuer, &token, &0);
+    client.deposit_revenue(&issuer, &token, &payment_token, &200_000, &2);
+
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    assert_eq!(client.get_claimable(&token, &holder), 200_000);
+    assert_eq!(client.claim(&holder, &token, &0), 200_000);
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 1_u64]);
+    let r = client.try_claim(&holder, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ClaimDelayNotElapsed)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    assert_eq!(client.get_claimable(&token, &holder), 100_000);
+    assert_eq!(client.claim(&holder, &token, &0), 100_000);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 0);
+    assert_eq!(balance(&env, &payment_token, &holder), 300_000);
+}
+
 #[test]
 fn set_claim_delay_emits_event() {
     let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
@@ -3398,7 +3444,7 @@ fn frozen_blocks_set_holder_share() {
 
 #[test]
 fn frozen_allows_claim() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -3443,10 +3489,11 @@ fn set_snapshot_config_stores_and_returns_config() {
 
 #[test]
 fn deposit_revenue_with_snapshot_succeeds_when_enabled() {
-    let (_env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
 
     client.set_snapshot_config(&issuer, &token, &true);
-    let snapshot_ref: u64 = 123456;
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+    let snapshot_ref: u64 = 1_000;
     let period_id: u64 = 1;
     let amount: i128 = 100_000;
 
@@ -3486,9 +3533,10 @@ fn deposit_revenue_with_snapshot_fails_when_disabled() {
 
 #[test]
 fn deposit_with_snapshot_enforces_monotonicity() {
-    let (_env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
 
     client.set_snapshot_config(&issuer, &token, &true);
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
 
     // First deposit at ref 100
     client.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &10_000, &1, &100);
@@ -3526,11 +3574,36 @@ fn deposit_with_snapshot_enforces_monotonicity() {
     assert_eq!(client.get_last_snapshot_ref(&issuer, &token), 101);
 }
 
+#[test]
+fn deposit_with_snapshot_rejects_reference_ahead_of_ledger() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+
+    client.set_snapshot_config(&issuer, &token, &true);
+    env.ledger().with_mut(|li| li.sequence_number = 500);
+
+    let r = client.try_deposit_revenue_with_snapshot(
+        &issuer,
+        &token,
+        &payment_token,
+        &10_000,
+        &1,
+        &501,
+    );
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::SnapshotInFuture)));
+    assert_eq!(client.get_period_count(&token), 0);
+    assert_eq!(client.get_last_snapshot_ref(&issuer, &token), 0);
+
+    // The current ledger itself is a valid snapshot
+    client.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &10_000, &1, &500);
+    assert_eq!(client.get_last_snapshot_ref(&issuer, &token), 500);
+}
+
 #[test]
 fn deposit_with_snapshot_emits_specialized_event() {
     let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
 
     client.set_snapshot_config(&issuer, &token, &true);
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
     let before = env.events().all().len();
 
     client.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &10_000, &1, &1000);
@@ -3561,7 +3634,7 @@ fn set_snapshot_config_requires_auth() {
     // No mock_all_auths
     let result = client.try_set_snapshot_config(&issuer, &token, &true);
     assert!(result.is_err());
-
+}
 
 // ===========================================================================
 // Testnet mode tests (#24)
@@ -4079,6 +4152,11 @@ fn issuer_transfer_blocked_when_frozen() {
     client.set_admin(&admin);
     client.freeze();
 
+    let r = client.try_propose_issuer_transfer(&token, &new_issuer);
+    assert!(r.is_err());
+    assert_eq!(client.get_pending_issuer_transfer(&token), None);
+}
+
 // ===========================================================================
 // Multisig admin pattern tests
 // ===========================================================================
@@ -4460,11 +4538,6 @@ fn multisig_multiple_proposals_independent() {
 fn multisig_get_proposal_nonexistent_returns_none() {
     let (_env, client, _owner1, _owner2, _owner3, _caller) = multisig_setup();
     assert!(client.get_proposal(&9999).is_none());
-
-// On-chain revenue distribution calculation (#4)
-// ===========================================================================
-    let result = client.try_propose_issuer_transfer(&token, &new_issuer);
-    assert!(result.is_err());
 }
 
 #[test]
@@ -4573,7 +4646,7 @@ fn issuer_transfer_new_issuer_can_set_claim_delay() {
 
 #[test]
 fn issuer_transfer_holders_can_still_claim() {
-    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
     let holder = Address::generate(&env);
     let new_issuer = Address::generate(&env);
 
@@ -4700,8 +4773,10 @@ fn testnet_mode_blacklist_operations_unaffected() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &token);
 
     client.set_admin(&admin);
     client.set_testnet_mode(&true);
@@ -4761,7 +4836,7 @@ fn pause_unpause_idempotence_and_events() {
     let client = make_client(&env);
     let admin = Address::generate(&env);
 
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     assert!(!client.is_paused());
 
     // Pause twice (idempotent)
@@ -4791,7 +4866,7 @@ fn register_blocked_while_paused() {
     let token = Address::generate(&env);
     let payout_asset = Address::generate(&env);
 
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.pause_admin(&admin);
     client.register_offering(&issuer, &token, &1_000, &payout_asset);
 }
@@ -4807,7 +4882,7 @@ fn report_blocked_while_paused() {
     let token = Address::generate(&env);
     let payout_asset = Address::generate(&env);
 
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     // Register before pausing
     client.register_offering(&issuer, &token, &1_000, &payout_asset);
     client.pause_admin(&admin);
@@ -4822,7 +4897,7 @@ fn pause_safety_role_works() {
     let admin = Address::generate(&env);
     let safety = Address::generate(&env);
 
-    client.initialize(&admin, &Some(safety.clone()));
+    client.initialize(&admin, &Some(safety.clone()), &None::<bool>);
     assert!(!client.is_paused());
 
     // Safety can pause
@@ -4844,7 +4919,7 @@ fn blacklist_add_blocked_while_paused() {
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.pause_admin(&admin);
     client.blacklist_add(&admin, &token, &investor);
 }
@@ -4859,24 +4934,58 @@ fn blacklist_remove_blocked_while_paused() {
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.pause_admin(&admin);
     client.blacklist_remove(&admin, &token, &investor);
- 
 }
+
 #[test]
-fn large_period_range_sums_correctly_full() {
+#[should_panic(expected = "contract is paused")]
+fn migrate_legacy_lists_blocked_while_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
 
-// ===========================================================================
-// On-chain revenue distribution calculation (#4)
-// ===========================================================================
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
+    client.pause_admin(&admin);
+    client.migrate_legacy_lists(&admin, &token, &0);
+}
 
 #[test]
-fn calculate_distribution_basic() {
-    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
-    let caller = Address::generate(&env);
-    let holder = Address::generate(&env);
-
+fn large_period_range_sums_correctly_full() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    let payout_asset = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000, &payout_asset);
+
+    for period_id in 1..=10_u64 {
+        client.report_revenue(
+            &issuer,
+            &token,
+            &payout_asset,
+            &(period_id as i128 * 100),
+            &period_id,
+            &false,
+        );
+    }
+    assert_eq!(client.get_revenue_range(&token, &1, &10), 5_500);
+}
+
+// ===========================================================================
+// On-chain revenue distribution calculation (#4)
+// ===========================================================================
+
+#[test]
+fn calculate_distribution_basic() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let caller = Address::generate(&env);
+    let holder = Address::generate(&env);
+
     let total_revenue = 1_000_000_i128;
     let total_supply = 10_000_i128;
     let holder_balance = 1_000_i128;
@@ -5013,14 +5122,6 @@ fn calculate_distribution_rounds_down_exact() {
     let client = make_client(&env);
     let issuer = Address::generate(&env);
     let token = Address::generate(&env);
-
-    for p in 1u64..=20u64 {
-        client.report_revenue(&issuer, &token, &100, &p, &false);
-    }
-
-    assert_eq!(client.get_revenue_range(&token, &1, &20), 2_000);
-    assert_eq!(client.get_revenue_range(&token, &1, &10), 1_000);
-    assert_eq!(client.get_revenue_range(&token, &11, &20), 1_000);
     let caller = Address::generate(&env);
     let holder = Address::generate(&env);
 
@@ -5112,8 +5213,6 @@ fn calculate_distribution_multiple_holders_sum() {
         &holder_c,
     );
 
-    assert_eq!(payout, 50_000);
-
     assert_eq!(payout_a, 25_000);
     assert_eq!(payout_b, 15_000);
     assert_eq!(payout_c, 10_000);
@@ -5331,7 +5430,7 @@ fn test_event_only_mode_register_and_report() {
 
     // Verify event emitted (skip checking EVENT_INIT)
     let events = env.events().all();
-    assert!(events.iter().any(|e| e.1.contains(symbol_short!("offer_reg"))));
+    assert!(events.iter().any(|e| e.1.contains(symbol_short!("offer_reg").to_val())));
 
     // Storage should be empty for this offering
     assert!(client.get_offering(&issuer, &token).is_none());
@@ -5341,15 +5440,15 @@ fn test_event_only_mode_register_and_report() {
     client.report_revenue(&issuer, &token, &payout_asset, &amount, &period_id, &false);
 
     let events = env.events().all();
-    assert!(events.iter().any(|e| e.1.contains(symbol_short!("rev_init"))));
-    assert!(events.iter().any(|e| e.1.contains(symbol_short!("rev_rep"))));
+    assert!(events.iter().any(|e| e.1.contains(symbol_short!("rev_init").to_val())));
+    assert!(events.iter().any(|e| e.1.contains(symbol_short!("rev_rep").to_val())));
 
     // Audit summary should NOT be updated
     assert!(client.get_audit_summary(&issuer, &token).is_none());
 }
 
 #[test]
-fn test_event_only_mode_blacklist() {
+fn test_event_only_mode_blacklist_and_whitelist() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -5357,20 +5456,31 @@ fn test_event_only_mode_blacklist() {
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
     client.initialize(&admin, &None, &Some(true));
 
     // Blacklist add should emit event but NOT persist
-    client.blacklist_add(&issuer, &token, &investor);
+    client.blacklist_add(&admin, &token, &investor);
 
     let events = env.events().all();
-    assert!(events.iter().any(|e| e.1.contains(symbol_short!("bl_add"))));
+    assert!(events.iter().any(|e| e.1.contains(symbol_short!("bl_add").to_val())));
 
     assert!(!client.is_blacklisted(&token, &investor));
-    assert_eq!(client.get_blacklist(&token).len(), 0);
+    assert_eq!(client.get_blacklist(&token, &0, &0).0.len(), 0);
+
+    // Whitelist changes likewise only emit events
+    client.whitelist_add(&admin, &token, &investor);
+    let events = env.events().all();
+    assert!(events.iter().any(|e| e.1.contains(symbol_short!("wl_add").to_val())));
+    assert!(!client.is_whitelisted(&token, &investor));
+    assert!(!client.is_whitelist_enabled(&token));
+    assert_eq!(client.get_whitelist_page(&token, &0, &0).0.len(), 0);
+
+    client.whitelist_remove(&admin, &token, &investor);
+    let events = env.events().all();
+    assert!(events.iter().any(|e| e.1.contains(symbol_short!("wl_rem").to_val())));
 }
 
 #[test]
@@ -5388,9 +5498,10 @@ fn test_event_only_mode_testnet_config() {
     client.set_testnet_mode(&true);
 
     let events = env.events().all();
-    assert!(events.iter().any(|e| e.1.contains(symbol_short!("test_mode"))));
+    assert!(events.iter().any(|e| e.1.contains(symbol_short!("test_mode").to_val())));
 
     assert!(!client.is_testnet_mode());
+}
 
 // ── Per-offering metadata storage tests (#8) ──────────────────
 
@@ -5495,7 +5606,7 @@ fn test_set_metadata_respects_freeze() {
     let issuer = Address::generate(&env);
     let token = Address::generate(&env);
 
-    client.initialize(&admin, &None);
+    client.initialize(&admin, &None, &None::<bool>);
     client.register_offering(&issuer, &token, &1000, &token);
     client.freeze();
 
@@ -5514,7 +5625,7 @@ fn test_set_metadata_respects_pause() {
     let issuer = Address::generate(&env);
     let token = Address::generate(&env);
 
-    client.initialize(&admin, &None);
+    client.initialize(&admin, &None, &None::<bool>);
     client.register_offering(&issuer, &token, &1000, &token);
     client.pause_admin(&admin);
 
@@ -5939,6 +6050,8 @@ mod regression {
     // ──────────────────────────────────────────────────────────────────────────
     // Add new regression tests below this line
     // ──────────────────────────────────────────────────────────────────────────
+}
+
 // ── Platform fee tests (#6) ─────────────────────────────────
 
 #[test]
@@ -5948,7 +6061,7 @@ fn default_platform_fee_is_zero() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     assert_eq!(client.get_platform_fee(), 0);
 }
 
@@ -5959,7 +6072,7 @@ fn set_and_get_platform_fee() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&250);
     assert_eq!(client.get_platform_fee(), 250);
 }
@@ -5971,7 +6084,7 @@ fn set_platform_fee_to_zero() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&500);
     client.set_platform_fee(&0);
     assert_eq!(client.get_platform_fee(), 0);
@@ -5984,7 +6097,7 @@ fn set_platform_fee_to_maximum() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&5000);
     assert_eq!(client.get_platform_fee(), 5000);
 }
@@ -5996,7 +6109,7 @@ fn set_platform_fee_above_maximum_fails() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     let result = client.try_set_platform_fee(&5001);
     assert!(result.is_err());
 }
@@ -6008,7 +6121,7 @@ fn update_platform_fee_multiple_times() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&100);
     assert_eq!(client.get_platform_fee(), 100);
     client.set_platform_fee(&200);
@@ -6024,7 +6137,7 @@ fn set_platform_fee_requires_admin() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&100);
 }
 
@@ -6035,7 +6148,7 @@ fn calculate_platform_fee_basic() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&250); // 2.5%
     let fee = client.calculate_platform_fee(&10_000);
     assert_eq!(fee, 250); // 10000 * 250 / 10000 = 250
@@ -6048,7 +6161,7 @@ fn calculate_platform_fee_with_zero_amount() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&500);
     let fee = client.calculate_platform_fee(&0);
     assert_eq!(fee, 0);
@@ -6061,7 +6174,7 @@ fn calculate_platform_fee_with_zero_fee() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     let fee = client.calculate_platform_fee(&10_000);
     assert_eq!(fee, 0);
 }
@@ -6073,7 +6186,7 @@ fn calculate_platform_fee_at_maximum_rate() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&5000); // 50%
     let fee = client.calculate_platform_fee(&10_000);
     assert_eq!(fee, 5_000);
@@ -6086,7 +6199,7 @@ fn calculate_platform_fee_precision() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&1); // 0.01%
     let fee = client.calculate_platform_fee(&1_000_000);
     assert_eq!(fee, 100); // 1000000 * 1 / 10000 = 100
@@ -6099,7 +6212,7 @@ fn platform_fee_only_admin_can_set() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&100);
 }
 
@@ -6110,7 +6223,7 @@ fn platform_fee_large_amount() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&100); // 1%
     let large_amount: i128 = 1_000_000_000_000;
     let fee = client.calculate_platform_fee(&large_amount);
@@ -6124,7 +6237,7 @@ fn platform_fee_integration_with_revenue() {
     let contract_id = env.register_contract(None, RevoraRevenueShare);
     let client = RevoraRevenueShareClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.initialize(&admin, &None::<Address>);
+    client.initialize(&admin, &None::<Address>, &None::<bool>);
     client.set_platform_fee(&500); // 5%
     let revenue: i128 = 100_000;
     let fee = client.calculate_platform_fee(&revenue);
@@ -6234,19 +6347,20 @@ fn get_blacklist_order_is_by_insertion() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let a = Address::generate(&env);
     let b = Address::generate(&env);
     let c = Address::generate(&env);
-    client.blacklist_add(&admin, &token, &a);
-    client.blacklist_add(&admin, &token, &b);
-    client.blacklist_add(&admin, &token, &c);
-    let list = client.get_blacklist(&token);
+    client.register_offering(&issuer, &token, &1_000, &token);
+    client.blacklist_add(&issuer, &token, &a);
+    client.blacklist_add(&issuer, &token, &b);
+    client.blacklist_add(&issuer, &token, &c);
+    let (list, _) = client.get_blacklist(&token, &0, &0);
     assert_eq!(list.len(), 3);
-    assert_eq!(list.get(0).unwrap(), a);
-    assert_eq!(list.get(1).unwrap(), b);
-    assert_eq!(list.get(2).unwrap(), c);
+    assert_eq!(list.get(0).unwrap().investor, a);
+    assert_eq!(list.get(1).unwrap().investor, b);
+    assert_eq!(list.get(2).unwrap().investor, c);
 }
 
 #[test]
@@ -6254,19 +6368,20 @@ fn get_blacklist_order_unchanged_after_remove() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
-    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
     let token = Address::generate(&env);
     let a = Address::generate(&env);
     let b = Address::generate(&env);
     let c = Address::generate(&env);
-    client.blacklist_add(&admin, &token, &a);
-    client.blacklist_add(&admin, &token, &b);
-    client.blacklist_add(&admin, &token, &c);
-    client.blacklist_remove(&admin, &token, &b);
-    let list = client.get_blacklist(&token);
+    client.register_offering(&issuer, &token, &1_000, &token);
+    client.blacklist_add(&issuer, &token, &a);
+    client.blacklist_add(&issuer, &token, &b);
+    client.blacklist_add(&issuer, &token, &c);
+    client.blacklist_remove(&issuer, &token, &b);
+    let (list, _) = client.get_blacklist(&token, &0, &0);
     assert_eq!(list.len(), 2);
-    assert_eq!(list.get(0).unwrap(), a);
-    assert_eq!(list.get(1).unwrap(), c);
+    assert_eq!(list.get(0).unwrap().investor, a);
+    assert_eq!(list.get(1).unwrap().investor, c);
 }
 
 #[test]
@@ -6277,6 +6392,7 @@ fn get_pending_periods_order_is_by_deposit_index() {
     client.deposit_revenue(&issuer, &token, &payment_token, &300, &30);
     let holder = Address::generate(&env);
     client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.grant_back_periods(&issuer, &token, &holder, &0);
     let periods = client.get_pending_periods(&token, &holder);
     assert_eq!(periods.len(), 3);
     assert_eq!(periods.get(0).unwrap(), 10);
@@ -6415,10 +6531,10 @@ fn invariant_random_step(
         }
         4 => {
             let holder = Address::generate(env);
-            client.blacklist_add(&issuer, &token, &holder);
+            let _ = client.try_blacklist_add(&issuer, &token, &holder);
         }
         5 => {
-            client.blacklist_remove(&issuer, &token, &issuer);
+            let _ = client.try_blacklist_remove(&issuer, &token, &issuer);
         }
         _ => {}
     }
@@ -6813,3 +6929,2288 @@ fn aggregation_stress_many_offerings() {
     assert_eq!(metrics.total_reported_revenue, 2_100_000);
     assert_eq!(metrics.total_report_count, 20);
 }
+
+// ── Merkle-root distribution tests ────────────────────────────
+
+fn merkle_leaf(env: &Env, leaf_index: u32, holder: &Address, amount: i128) -> BytesN<32> {
+    env.crypto().sha256(&(leaf_index, holder.clone(), amount).to_xdr(env))
+}
+
+fn merkle_parent(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut buf = Bytes::from_array(env, &lo.to_array());
+    buf.append(&Bytes::from_array(env, &hi.to_array()));
+    env.crypto().sha256(&buf)
+}
+
+/// Two-leaf tree: returns (root, leaf0, leaf1).
+fn merkle_two_leaves(
+    env: &Env,
+    holder_a: &Address,
+    amount_a: i128,
+    holder_b: &Address,
+    amount_b: i128,
+) -> (BytesN<32>, BytesN<32>, BytesN<32>) {
+    let leaf0 = merkle_leaf(env, 0, holder_a, amount_a);
+    let leaf1 = merkle_leaf(env, 1, holder_b, amount_b);
+    (merkle_parent(env, &leaf0, &leaf1), leaf0, leaf1)
+}
+
+#[test]
+fn merkle_claim_pays_each_leaf() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let (root, leaf0, leaf1) = merkle_two_leaves(&env, &holder_a, 30_000, &holder_b, 70_000);
+
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &100_000, &1, &root);
+    assert_eq!(client.get_merkle_root(&token, &1), Some(root));
+
+    let paid_a = client.claim_merkle(&holder_a, &token, &1, &0, &30_000, &vec![&env, leaf1]);
+    let paid_b = client.claim_merkle(&holder_b, &token, &1, &1, &70_000, &vec![&env, leaf0]);
+
+    assert_eq!(paid_a, 30_000);
+    assert_eq!(paid_b, 70_000);
+    assert_eq!(balance(&env, &payment_token, &holder_a), 30_000);
+    assert_eq!(balance(&env, &payment_token, &holder_b), 70_000);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 0);
+    assert!(client.is_merkle_claimed(&token, &1, &0));
+}
+
+#[test]
+fn merkle_claim_rejects_double_claim() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let (root, _leaf0, leaf1) = merkle_two_leaves(&env, &holder_a, 30_000, &holder_b, 70_000);
+
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &100_000, &1, &root);
+    client.claim_merkle(&holder_a, &token, &1, &0, &30_000, &vec![&env, leaf1.clone()]);
+
+    let result = client.try_claim_merkle(&holder_a, &token, &1, &0, &30_000, &vec![&env, leaf1]);
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::MerkleLeafAlreadyClaimed)));
+}
+
+#[test]
+fn merkle_claim_rejects_wrong_amount_or_holder() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let (root, _leaf0, leaf1) = merkle_two_leaves(&env, &holder_a, 30_000, &holder_b, 70_000);
+
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &100_000, &1, &root);
+
+    let inflated =
+        client.try_claim_merkle(&holder_a, &token, &1, &0, &31_000, &vec![&env, leaf1.clone()]);
+    assert!(matches!(inflated.err().unwrap(), Ok(RevoraError::InvalidMerkleProof)));
+
+    let thief = Address::generate(&env);
+    let stolen = client.try_claim_merkle(&thief, &token, &1, &0, &30_000, &vec![&env, leaf1]);
+    assert!(matches!(stolen.err().unwrap(), Ok(RevoraError::InvalidMerkleProof)));
+}
+
+#[test]
+fn merkle_claim_cannot_exceed_period_deposit() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    // Root commits to more than the deposit: the second claim must be refused.
+    let (root, leaf0, leaf1) = merkle_two_leaves(&env, &holder_a, 60_000, &holder_b, 60_000);
+
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &100_000, &1, &root);
+    client.claim_merkle(&holder_a, &token, &1, &0, &60_000, &vec![&env, leaf1]);
+
+    let result = client.try_claim_merkle(&holder_b, &token, &1, &1, &60_000, &vec![&env, leaf0]);
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::InvalidAmount)));
+}
+
+#[test]
+fn merkle_periods_are_skipped_by_share_claims() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+    let (root, _leaf0, _leaf1) = merkle_two_leaves(&env, &holder, 10_000, &other, 10_000);
+
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &20_000, &2, &root);
+
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 1_u64]);
+    assert_eq!(client.get_claimable(&token, &holder), 50_000);
+    assert_eq!(client.claim(&holder, &token, &0), 50_000);
+}
+
+#[test]
+fn merkle_claim_without_root_fails() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    let result = client.try_claim_merkle(&holder, &token, &1, &0, &1_000, &Vec::new(&env));
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::InvalidMerkleProof)));
+}
+
+// ── Holder-share checkpoint tests ─────────────────────────────
+
+#[test]
+fn share_change_is_not_retroactive_for_unclaimed_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &5_000); // 50%
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &2);
+
+    // Share drops after two deposits; only period 3 should use 10%
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &3);
+
+    assert_eq!(client.get_claimable(&token, &holder), 110_000);
+    assert_eq!(client.claim(&holder, &token, &0), 110_000);
+}
+
+#[test]
+fn share_increase_does_not_inflate_past_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &9_000);
+
+    assert_eq!(client.get_claimable(&token, &holder), 10_000);
+    assert_eq!(client.get_holder_share_at(&token, &holder, &0), 1_000);
+    assert_eq!(client.get_holder_share_at(&token, &holder, &1), 9_000);
+}
+
+#[test]
+fn holder_with_share_removed_can_still_claim_earlier_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &0);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &2);
+
+    assert_eq!(client.claim(&holder, &token, &0), 20_000);
+    assert_eq!(balance(&env, &payment_token, &holder), 20_000);
+}
+
+#[test]
+fn share_changes_between_deposits_collapse_into_one_checkpoint() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &2_000);
+    client.set_holder_share(&issuer, &token, &holder, &3_000);
+
+    let checkpoints = client.get_share_checkpoints(&token, &holder);
+    assert_eq!(checkpoints.len(), 2);
+    assert_eq!(
+        checkpoints.get(1).unwrap(),
+        ShareCheckpoint { from_period_index: 1, share_bps: 3_000 }
+    );
+}
+
+// ── Entitlement start tests ───────────────────────────────────
+
+#[test]
+fn new_holder_is_not_entitled_to_earlier_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &2);
+
+    assert_eq!(client.get_entitlement_start(&token, &holder), 1);
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 2_u64]);
+    assert_eq!(client.get_claimable(&token, &holder), 50_000);
+    assert_eq!(client.claim(&holder, &token, &0), 50_000);
+}
+
+#[test]
+fn new_holder_with_no_later_deposits_has_nothing_to_claim() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    let result = client.try_claim(&holder, &token, &0);
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::NoPendingClaims)));
+}
+
+#[test]
+fn grant_back_periods_opens_earlier_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &2);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.grant_back_periods(&issuer, &token, &holder, &1);
+
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 2_u64]);
+    assert_eq!(client.claim(&holder, &token, &0), 10_000);
+}
+
+#[test]
+fn grant_back_periods_before_share_is_kept() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.grant_back_periods(&issuer, &token, &holder, &0);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+
+    assert_eq!(client.get_entitlement_start(&token, &holder), 0);
+    assert_eq!(client.get_claimable(&token, &holder), 10_000);
+}
+
+#[test]
+fn grant_back_periods_rejects_later_or_future_start() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.grant_back_periods(&issuer, &token, &holder, &0);
+
+    let later = client.try_grant_back_periods(&issuer, &token, &holder, &1);
+    assert!(matches!(later.err().unwrap(), Ok(RevoraError::InvalidPeriodId)));
+    let future = client.try_grant_back_periods(&issuer, &token, &holder, &2);
+    assert!(matches!(future.err().unwrap(), Ok(RevoraError::InvalidPeriodId)));
+}
+
+#[test]
+fn share_change_does_not_move_entitlement_start() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &2_000);
+
+    assert_eq!(client.get_entitlement_start(&token, &holder), 0);
+    assert_eq!(client.get_claimable(&token, &holder), 10_000);
+}
+
+// ── Share allocation cap tests ────────────────────────────────
+
+#[test]
+fn set_holder_share_rejects_total_over_revenue_share() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    let (payment_token, _pt_admin) = create_payment_token(&env);
+    client.register_offering(&issuer, &token, &3_000, &payment_token);
+
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &2_000);
+
+    let result = client.try_set_holder_share(&issuer, &token, &holder_b, &1_500);
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::ShareAllocationExceeded)));
+    assert_eq!(client.get_holder_share(&token, &holder_b), 0);
+    assert_eq!(client.get_total_allocated_bps(&token), 2_000);
+    assert_eq!(client.get_unallocated_bps(&token), 1_000);
+}
+
+#[test]
+fn lowering_a_share_frees_allocation() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder_a, &10_000);
+    assert!(client.try_set_holder_share(&issuer, &token, &holder_b, &1).is_err());
+
+    client.set_holder_share(&issuer, &token, &holder_a, &6_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &4_000);
+    assert_eq!(client.get_total_allocated_bps(&token), 10_000);
+    assert_eq!(client.get_unallocated_bps(&token), 0);
+}
+
+#[test]
+fn get_unallocated_bps_unknown_offering_is_zero() {
+    let (env, client, _issuer) = setup();
+    let token = Address::generate(&env);
+    assert_eq!(client.get_unallocated_bps(&token), 0);
+}
+
+// ── Solvency accounting tests ─────────────────────────────────
+
+#[test]
+fn liabilities_track_deposits_and_claims() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &6_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    let before = client.get_offering_liabilities(&token);
+    assert_eq!(before.total_deposited, 100_000);
+    assert_eq!(before.total_claimed, 0);
+    assert_eq!(before.outstanding, 100_000);
+
+    client.claim(&holder, &token, &0);
+    let after = client.get_offering_liabilities(&token);
+    assert_eq!(after.total_claimed, 60_000);
+    assert_eq!(after.outstanding, 40_000);
+
+    let report = client.get_solvency(&payment_token);
+    assert_eq!(report.liabilities, 40_000);
+    assert_eq!(report.balance, 40_000);
+    assert_eq!(report.shortfall, 0);
+}
+
+#[test]
+fn solvency_sums_offerings_sharing_payment_token() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let token_b = Address::generate(&env);
+    client.register_offering(&issuer, &token_b, &10_000, &payment_token);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.deposit_revenue(&issuer, &token_b, &payment_token, &50_000, &1);
+
+    assert_eq!(client.get_offering_liabilities(&token_b).outstanding, 50_000);
+    let report = client.get_solvency(&payment_token);
+    assert_eq!(report.liabilities, 150_000);
+    assert_eq!(report.balance, 150_000);
+}
+
+#[test]
+fn solvency_reports_shortfall_when_balance_drops() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    // Funds leave the contract outside of claim (e.g. clawback by the asset issuer)
+    token::Client::new(&env, &payment_token).burn(&contract_id, &30_000);
+
+    let report = client.get_solvency(&payment_token);
+    assert_eq!(report.liabilities, 100_000);
+    assert_eq!(report.balance, 70_000);
+    assert_eq!(report.shortfall, 30_000);
+}
+
+#[test]
+fn merkle_claims_reduce_liabilities() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let (root, _leaf0, leaf1) = merkle_two_leaves(&env, &holder_a, 30_000, &holder_b, 70_000);
+
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &100_000, &1, &root);
+    client.claim_merkle(&holder_a, &token, &1, &0, &30_000, &vec![&env, leaf1]);
+
+    assert_eq!(client.get_offering_liabilities(&token).outstanding, 70_000);
+    assert_eq!(client.get_solvency(&payment_token).liabilities, 70_000);
+}
+
+// ── Accumulator mode tests ────────────────────────────────────
+
+#[test]
+fn accumulator_claim_settles_every_pending_period_at_once() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_accumulator_mode(&issuer, &token, &true);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &6_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &4_000);
+
+    // More periods than MAX_CLAIM_PERIODS
+    for period_id in 1..=60_u64 {
+        client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &period_id);
+    }
+
+    assert_eq!(client.get_claimable(&token, &holder_a), 36_000);
+    assert_eq!(client.claim(&holder_a, &token, &0), 36_000);
+    assert_eq!(client.claim(&holder_b, &token, &0), 24_000);
+    assert_eq!(client.get_pending_periods(&token, &holder_a).len(), 0);
+    assert_eq!(client.get_offering_liabilities(&token).outstanding, 0);
+}
+
+#[test]
+fn accumulator_settles_before_share_change() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_accumulator_mode(&issuer, &token, &true);
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &2);
+
+    assert_eq!(client.get_claimable(&token, &holder), 60_000);
+    assert_eq!(client.claim(&holder, &token, &0), 60_000);
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    let result = client.try_claim(&holder, &token, &0);
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::NoPendingClaims)));
+}
+
+#[test]
+fn accumulator_new_holder_only_earns_later_deposits() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_accumulator_mode(&issuer, &token, &true);
+    let holder = Address::generate(&env);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &2);
+    assert_eq!(client.get_claimable(&token, &holder), 50_000);
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 2_u64]);
+}
+
+#[test]
+fn accumulator_mode_conflicts_are_rejected() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_accumulator_mode(&issuer, &token, &true);
+    assert!(client.is_accumulator_mode(&token));
+
+    let delay = client.try_set_claim_delay(&issuer, &token, &3_600);
+    assert!(matches!(delay.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    let switch = client.try_set_accumulator_mode(&issuer, &token, &false);
+    assert!(matches!(switch.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+
+    let holder = Address::generate(&env);
+    let grant = client.try_grant_back_periods(&issuer, &token, &holder, &0);
+    assert!(matches!(grant.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+}
+
+// ── claim_many tests ──────────────────────────────────────────
+
+#[test]
+fn claim_many_makes_one_transfer_per_payment_token() {
+    let (env, client, issuer, token_a, payment_token, contract_id) = claim_setup();
+    let token_b = Address::generate(&env);
+    let token_c = Address::generate(&env);
+    let (other_pt, other_admin) = create_payment_token(&env);
+    client.register_offering(&issuer, &token_b, &10_000, &payment_token);
+    client.register_offering(&issuer, &token_c, &10_000, &other_pt);
+    mint_tokens(&env, &other_pt, &other_admin, &issuer, &1_000_000);
+
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token_a, &holder, &1_000);
+    client.set_holder_share(&issuer, &token_b, &holder, &2_000);
+    client.set_holder_share(&issuer, &token_c, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token_a, &payment_token, &100_000, &1);
+    client.deposit_revenue(&issuer, &token_b, &payment_token, &100_000, &1);
+    client.deposit_revenue(&issuer, &token_c, &other_pt, &100_000, &1);
+
+    let results = client.claim_many(
+        &holder,
+        &vec![&env, (token_a.clone(), 0_u32), (token_b.clone(), 0_u32), (token_c.clone(), 0_u32)],
+    );
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results.get(0).unwrap(),
+        ClaimResult { token: token_a, amount: 10_000, error: None }
+    );
+    assert_eq!(results.get(1).unwrap().amount, 20_000);
+    assert_eq!(results.get(2).unwrap().amount, 50_000);
+    assert_eq!(balance(&env, &payment_token, &holder), 30_000);
+    assert_eq!(balance(&env, &other_pt, &holder), 50_000);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 170_000);
+}
+
+#[test]
+fn claim_many_reports_failures_per_entry() {
+    let (env, client, issuer, token_a, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let token_b = Address::generate(&env);
+    let token_c = Address::generate(&env);
+    client.register_offering(&issuer, &token_b, &10_000, &payment_token);
+    client.register_offering(&issuer, &token_c, &10_000, &payment_token);
+
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token_a, &holder, &1_000);
+    client.set_holder_share(&issuer, &token_b, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token_a, &payment_token, &100_000, &1);
+    client.deposit_revenue(&issuer, &token_b, &payment_token, &100_000, &1);
+    client.blacklist_add(&issuer, &token_b, &holder);
+
+    let results = client.claim_many(
+        &holder,
+        &vec![&env, (token_a.clone(), 0_u32), (token_b.clone(), 0_u32), (token_c.clone(), 0_u32)],
+    );
+
+    assert_eq!(results.get(0).unwrap().error, None);
+    assert_eq!(results.get(1).unwrap().error, Some(RevoraError::HolderBlacklisted as u32));
+    assert_eq!(results.get(2).unwrap().error, Some(RevoraError::NoPendingClaims as u32));
+    assert_eq!(balance(&env, &payment_token, &holder), 10_000);
+    // The failed entry is still claimable once the holder is cleared
+    assert_eq!(client.get_pending_periods(&token_b, &holder).len(), 1);
+}
+
+#[test]
+fn claim_many_rejects_more_than_ten_entries() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    let mut claims = Vec::new(&env);
+    for _ in 0..11 {
+        claims.push_back((token.clone(), 0_u32));
+    }
+    let r = client.try_claim_many(&holder, &claims);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::LimitReached)));
+    assert_eq!(balance(&env, &payment_token, &holder), 0);
+
+    claims.pop_back();
+    assert_eq!(client.claim_many(&holder, &claims).len(), 10);
+    assert_eq!(balance(&env, &payment_token, &holder), 10_000);
+}
+
+// ── Push distribution tests ───────────────────────────────────
+
+#[test]
+fn push_distribution_pages_through_registered_holders() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let keeper = Address::generate(&env);
+    client.set_keeper(&issuer, &token, &Some(keeper.clone()));
+
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let holder_c = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &1_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &2_000);
+    client.set_holder_share(&issuer, &token, &holder_c, &3_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    assert_eq!(client.get_holder_count(&token), 3);
+
+    let first = client.push_distribution(&keeper, &token, &None, &0, &0, &2);
+    assert_eq!(first.paid, vec![&env, (holder_a.clone(), 10_000), (holder_b.clone(), 20_000)]);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.push_distribution(&issuer, &token, &None, &0, &2, &2);
+    assert_eq!(second.paid, vec![&env, (holder_c.clone(), 30_000)]);
+    assert_eq!(second.next_cursor, None);
+
+    assert_eq!(balance(&env, &payment_token, &holder_c), 30_000);
+    assert_eq!(client.get_pending_periods(&token, &holder_a).len(), 0);
+}
+
+#[test]
+fn push_distribution_skips_and_reports_blacklisted_holders() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &1_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.blacklist_add(&issuer, &token, &holder_b);
+
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.paid, vec![&env, (holder_a, 10_000)]);
+    assert_eq!(result.skipped_blacklisted, vec![&env, holder_b.clone()]);
+    assert_eq!(balance(&env, &payment_token, &holder_b), 0);
+    assert_eq!(client.get_pending_periods(&token, &holder_b).len(), 1);
+}
+
+#[test]
+fn push_distribution_requires_issuer_or_keeper() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    let stranger = Address::generate(&env);
+    let result = client.try_push_distribution(&stranger, &token, &None, &0, &0, &0);
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+}
+
+#[test]
+fn push_distribution_reports_failed_holders() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.set_claim_delay(&issuer, &token, &100);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.paid.len(), 0);
+    assert_eq!(
+        result.failed,
+        vec![&env, (holder.clone(), RevoraError::ClaimDelayNotElapsed as u32)]
+    );
+}
+
+#[test]
+fn push_distribution_event_counts_each_skip_reason() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let whitelisted = Address::generate(&env);
+    let blacklisted = Address::generate(&env);
+    let sanctioned = Address::generate(&env);
+    let unlisted = Address::generate(&env);
+    for holder in [&whitelisted, &blacklisted, &sanctioned, &unlisted] {
+        client.set_holder_share(&issuer, &token, holder, &2_500);
+    }
+    client.set_claim_delay(&issuer, &token, &100);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.whitelist_add(&issuer, &token, &whitelisted);
+    client.blacklist_add(&issuer, &token, &blacklisted);
+    client.sanction_add(&admin, &sanctioned);
+
+    // The whitelisted holder's claim fails on the delay
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.skipped_not_whitelisted, vec![&env, unlisted]);
+    assert_eq!(result.failed.len(), 1);
+    assert!(env.events().all().contains((
+        contract_id.clone(),
+        (symbol_short!("push"), issuer.clone(), token.clone()).into_val(&env),
+        (0_u32, 1_u32, 1_u32, 1_u32, 1_u32, None::<u32>).into_val(&env),
+    )));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.paid, vec![&env, (whitelisted, 25_000)]);
+    assert!(env.events().all().contains((
+        contract_id,
+        (symbol_short!("push"), issuer.clone(), token.clone()).into_val(&env),
+        (1_u32, 1_u32, 1_u32, 1_u32, 0_u32, None::<u32>).into_val(&env),
+    )));
+}
+
+#[test]
+fn push_distribution_pays_a_single_chosen_period() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &4_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &6_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &2_000, &2);
+
+    let result = client.push_distribution(&issuer, &token, &Some(2), &0, &0, &0);
+    assert_eq!(result.paid, vec![&env, (holder_a.clone(), 800), (holder_b.clone(), 1_200)]);
+    assert_eq!(client.get_pending_periods(&token, &holder_a), vec![&env, 1_u64]);
+
+    // Holders already paid from the period are skipped silently
+    let result = client.push_distribution(&issuer, &token, &Some(2), &0, &0, &0);
+    assert_eq!(result.paid.len(), 0);
+    assert_eq!(result.failed.len(), 0);
+    let r = client.try_push_distribution(&issuer, &token, &Some(3), &0, &0, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::InvalidPeriodId)));
+
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.paid, vec![&env, (holder_a.clone(), 400), (holder_b, 600)]);
+    assert_eq!(client.get_pending_periods(&token, &holder_a).len(), 0);
+    assert_eq!(balance(&env, &payment_token, &holder_a), 1_200);
+}
+
+#[test]
+fn register_holders_backfills_holders_set_before_the_registry() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    let legacy = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    // A share written before holders were registered
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::HolderShare(token.clone(), legacy.clone()), &1_000_u32);
+    });
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    assert_eq!(client.get_holder_count(&token), 0);
+
+    let added = client.register_holders(&issuer, &token, &vec![&env, legacy.clone(), stranger]);
+    assert_eq!(added, 1);
+    assert_eq!(client.register_holders(&issuer, &token, &vec![&env, legacy.clone()]), 0);
+    assert_eq!(client.get_holders_page(&token, &0, &0).0, vec![&env, legacy.clone()]);
+
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.paid, vec![&env, (legacy.clone(), 10_000)]);
+
+    // A later share change does not register the holder a second time
+    client.set_holder_share(&issuer, &token, &legacy, &2_000);
+    assert_eq!(client.get_holder_count(&token), 1);
+}
+
+#[test]
+fn register_holders_requires_issuer_or_keeper() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    let stranger = Address::generate(&env);
+    let r = client.try_register_holders(&stranger, &token, &vec![&env, stranger.clone()]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+}
+
+// ── Claim deadline and sweep tests ────────────────────────────
+
+#[test]
+fn expired_period_is_skipped_and_swept_to_issuer() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_claim_deadline(&issuer, &token, &1_000);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &4_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &6_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.claim(&holder_a, &token, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.get_claimable(&token, &holder_b), 0);
+    assert_eq!(client.get_pending_periods(&token, &holder_b).len(), 0);
+
+    let issuer_before = balance(&env, &payment_token, &issuer);
+    assert_eq!(client.sweep_expired_period(&issuer, &token, &1), 60_000);
+    assert_eq!(balance(&env, &payment_token, &issuer), issuer_before + 60_000);
+    assert_eq!(client.claim(&holder_b, &token, &0), 0);
+
+    let liabilities = client.get_offering_liabilities(&token);
+    assert_eq!(liabilities.total_swept, 60_000);
+    assert_eq!(liabilities.outstanding, 0);
+}
+
+#[test]
+fn sweep_goes_to_treasury_once_and_only_after_deadline() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let treasury = Address::generate(&env);
+    client.set_claim_deadline(&issuer, &token, &1_000);
+    client.set_treasury(&issuer, &token, &Some(treasury.clone()));
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    let early = client.try_sweep_expired_period(&issuer, &token, &1);
+    assert!(matches!(early.err().unwrap(), Ok(RevoraError::PeriodNotExpired)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.sweep_expired_period(&issuer, &token, &1);
+    assert_eq!(balance(&env, &payment_token, &treasury), 100_000);
+
+    let again = client.try_sweep_expired_period(&issuer, &token, &1);
+    assert!(matches!(again.err().unwrap(), Ok(RevoraError::PeriodAlreadySwept)));
+}
+
+#[test]
+fn changing_the_deadline_does_not_affect_deposited_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.set_claim_deadline(&issuer, &token, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &2);
+
+    // Shortening the deadline does not expire either period retroactively
+    client.set_claim_deadline(&issuer, &token, &1);
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    let r = client.try_sweep_expired_period(&issuer, &token, &1);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::PeriodNotExpired)));
+    let r = client.try_sweep_expired_period(&issuer, &token, &2);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::PeriodNotExpired)));
+    assert_eq!(client.get_claimable(&token, &holder), 20_000);
+
+    // Period 2 still expires on the deadline it was deposited with
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.claim(&holder, &token, &0), 10_000);
+    assert_eq!(client.sweep_expired_period(&issuer, &token, &2), 100_000);
+}
+
+#[test]
+fn swept_period_stays_closed_when_the_deadline_is_removed() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let (root, _leaf0, leaf1) = merkle_two_leaves(&env, &holder, 30_000, &issuer, 70_000);
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.set_claim_deadline(&issuer, &token, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &100_000, &2, &root);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.sweep_expired_period(&issuer, &token, &1);
+    client.sweep_expired_period(&issuer, &token, &2);
+    client.set_claim_deadline(&issuer, &token, &0);
+
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 0);
+    let r = client.try_claim_periods(&holder, &token, &vec![&env, 1_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ClaimDeadlinePassed)));
+    let r = client.try_claim_merkle(&holder, &token, &2, &0, &30_000, &vec![&env, leaf1]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ClaimDeadlinePassed)));
+    assert_eq!(client.claim(&holder, &token, &0), 0);
+    assert_eq!(balance(&env, &payment_token, &holder), 0);
+}
+
+#[test]
+fn merkle_claim_after_deadline_fails() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let (root, _leaf0, leaf1) = merkle_two_leaves(&env, &holder_a, 30_000, &holder_b, 70_000);
+    client.set_claim_deadline(&issuer, &token, &500);
+    client.deposit_revenue_with_merkle_root(&issuer, &token, &payment_token, &100_000, &1, &root);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    let result = client.try_claim_merkle(&holder_a, &token, &1, &0, &30_000, &vec![&env, leaf1]);
+    assert!(matches!(result.err().unwrap(), Ok(RevoraError::ClaimDeadlinePassed)));
+    assert_eq!(client.sweep_expired_period(&issuer, &token, &1), 100_000);
+}
+
+// ── Platform fee collection tests ─────────────────────────────
+
+#[test]
+fn deposit_collects_platform_fee_and_holders_share_net() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    client.set_platform_fee(&1_000); // 10%
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    assert_eq!(client.get_platform_fees_accrued(&payment_token), 10_000);
+    assert_eq!(client.get_claimable(&token, &holder), 45_000);
+    let report = client.get_solvency(&payment_token);
+    assert_eq!(report.liabilities, 100_000);
+    assert_eq!(report.shortfall, 0);
+}
+
+#[test]
+fn deposit_event_carries_gross_and_net_amounts() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    client.set_platform_fee(&1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    let rev_dep = (
+        contract_id,
+        (symbol_short!("rev_dep"), issuer, token).into_val(&env),
+        (payment_token, 100_000_i128, 1_u64, 90_000_i128).into_val(&env),
+    );
+    assert!(env.events().all().contains(rev_dep));
+}
+
+#[test]
+fn testnet_mode_does_not_waive_platform_fee() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    client.set_testnet_mode(&true);
+    client.set_platform_fee(&1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    assert_eq!(client.get_platform_fees_accrued(&payment_token), 10_000);
+}
+
+#[test]
+fn withdraw_platform_fees_pays_out_and_resets_ledger() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    let fee_wallet = Address::generate(&env);
+    client.set_admin(&admin);
+    client.set_platform_fee(&250);
+    client.deposit_revenue(&issuer, &token, &payment_token, &200_000, &1);
+
+    assert_eq!(client.withdraw_platform_fees(&payment_token, &fee_wallet), 5_000);
+    assert_eq!(balance(&env, &payment_token, &fee_wallet), 5_000);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 195_000);
+    assert_eq!(client.get_platform_fees_accrued(&payment_token), 0);
+    assert_eq!(client.withdraw_platform_fees(&payment_token, &fee_wallet), 0);
+}
+
+// ── Rounding in claim tests ───────────────────────────────────
+
+#[test]
+fn claim_applies_offering_rounding_mode() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &3_333);
+    client.set_rounding_mode(&issuer, &token, &RoundingMode::RoundHalfUp);
+    client.deposit_revenue(&issuer, &token, &payment_token, &15, &1);
+
+    // 15 * 0.3333 = 4.9995: truncation pays 4, half-up pays 5
+    assert_eq!(client.get_claimable(&token, &holder), 5);
+    assert_eq!(client.claim(&holder, &token, &0), 5);
+}
+
+#[test]
+fn round_half_up_never_pays_out_more_than_the_deposit() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &5_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &5_000);
+    client.set_rounding_mode(&issuer, &token, &RoundingMode::RoundHalfUp);
+    client.deposit_revenue(&issuer, &token, &payment_token, &3, &1);
+
+    // Rounding each 1.5 up to 2 would pay out 4, so the whole period truncates
+    assert_eq!(client.claim(&holder_b, &token, &0), 1);
+    assert_eq!(client.claim(&holder_a, &token, &0), 1);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 1);
+}
+
+#[test]
+fn round_half_up_period_rounds_every_holder_alike() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &4_500);
+    client.set_holder_share(&issuer, &token, &holder_b, &4_500);
+    client.set_rounding_mode(&issuer, &token, &RoundingMode::RoundHalfUp);
+    client.deposit_revenue(&issuer, &token, &payment_token, &30, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10, &2);
+
+    // Period 1: 13.5 rounds up to 14 for both (28 of 30). Period 2: 4.5 would round
+    // up to 5, but the 1 unallocated unit cannot cover a round-up for each holder,
+    // so the period truncates to 4 for both, in either claim order.
+    assert_eq!(client.claim(&holder_b, &token, &0), 18);
+    assert_eq!(client.claim(&holder_a, &token, &0), 18);
+}
+
+// ── Rounding dust tests ───────────────────────────────────────
+
+#[test]
+fn settle_period_dust_records_truncation_remainder() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    let holders = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for (holder, share) in holders.iter().zip([3_333_u32, 3_333, 3_334]) {
+        client.set_holder_share(&issuer, &token, holder, &share);
+    }
+    client.deposit_revenue(&issuer, &token, &payment_token, &100, &1);
+    for holder in holders.iter() {
+        assert_eq!(client.claim(holder, &token, &0), 33);
+    }
+
+    // The full 10_000 bps were paid 3 * 33 = 99; 1 unit was lost to truncation
+    assert_eq!(client.settle_period_dust(&issuer, &token, &1), 1);
+    assert_eq!(client.get_period_dust(&token, &1), Some(1));
+    assert_eq!(client.get_dust_ledger(&token).pending, 1);
+    assert_eq!(client.get_total_dust(&payment_token), 1);
+
+    let solvency = client.get_solvency(&payment_token);
+    assert_eq!(solvency.liabilities, balance(&env, &payment_token, &contract_id));
+    assert_eq!(client.get_offering_liabilities(&token).outstanding, 0);
+
+    let r = client.try_settle_period_dust(&issuer, &token, &1);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DustAlreadySettled)));
+}
+
+#[test]
+fn reclaim_dust_pays_issuer() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &3_333);
+    client.set_holder_share(&issuer, &token, &holder_b, &6_667);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100, &1);
+    assert_eq!(client.claim(&holder_a, &token, &0), 33);
+    assert_eq!(client.claim(&holder_b, &token, &0), 66);
+    assert_eq!(client.settle_period_dust(&issuer, &token, &1), 1);
+
+    let issuer_before = balance(&env, &payment_token, &issuer);
+    assert_eq!(client.reclaim_dust(&issuer, &token), 1);
+    assert_eq!(balance(&env, &payment_token, &issuer), issuer_before + 1);
+    assert_eq!(client.get_total_dust(&payment_token), 0);
+    assert_eq!(client.get_dust_ledger(&token).reclaimed, 1);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 0);
+    assert_eq!(client.get_offering_liabilities(&token).outstanding, 0);
+}
+
+#[test]
+fn period_dust_excludes_unallocated_and_unclaimed_shares() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    let holders = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for holder in holders.iter() {
+        client.set_holder_share(&issuer, &token, holder, &3_000);
+    }
+    client.deposit_revenue(&issuer, &token, &payment_token, &5, &1);
+
+    // Nothing paid yet, so nothing to settle; 10% of the period is unallocated
+    assert_eq!(client.settle_period_dust(&issuer, &token, &1), 0);
+    assert_eq!(client.claim(&holders[0], &token, &0), 1);
+    assert_eq!(client.claim(&holders[1], &token, &0), 1);
+    // 2 * 1.5 owed, 2 paid
+    assert_eq!(client.settle_period_dust(&issuer, &token, &1), 1);
+    let r = client.try_settle_period_dust(&issuer, &token, &1);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DustAlreadySettled)));
+
+    // The last holder's 1.5 brings the owed total to 4 (of 4.5): nothing new
+    assert_eq!(client.claim(&holders[2], &token, &0), 1);
+    let r = client.try_settle_period_dust(&issuer, &token, &1);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DustAlreadySettled)));
+    assert_eq!(client.get_period_dust(&token, &1), Some(1));
+    assert_eq!(balance(&env, &payment_token, &contract_id), 2);
+}
+
+#[test]
+fn rolled_dust_is_added_to_next_deposit() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &5_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &101, &1);
+    assert_eq!(client.claim(&holder_a, &token, &0), 50);
+    assert_eq!(client.claim(&holder_b, &token, &0), 50);
+    assert_eq!(client.settle_period_dust(&issuer, &token, &1), 1);
+
+    assert_eq!(client.roll_dust_forward(&issuer, &token), 1);
+    assert_eq!(client.get_dust_ledger(&token).rolled, 1);
+    assert_eq!(client.reclaim_dust(&issuer, &token), 0);
+
+    // Period 2 shares 99 + 1 rolled = 100
+    client.deposit_revenue(&issuer, &token, &payment_token, &99, &2);
+    assert_eq!(client.get_total_dust(&payment_token), 0);
+    assert_eq!(client.claim(&holder_a, &token, &0), 50);
+    assert_eq!(client.claim(&holder_b, &token, &0), 50);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 0);
+}
+
+#[test]
+fn settled_dust_is_excluded_from_sweep() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    client.set_claim_deadline(&issuer, &token, &1_000);
+    let holders = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for holder in holders.iter() {
+        client.set_holder_share(&issuer, &token, holder, &3_000);
+    }
+    client.deposit_revenue(&issuer, &token, &payment_token, &5, &1);
+    for holder in holders.iter() {
+        assert_eq!(client.claim(holder, &token, &0), 1);
+    }
+    assert_eq!(client.settle_period_dust(&issuer, &token, &1), 1);
+
+    // 5 - 3 paid - 1 dust
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(client.sweep_expired_period(&issuer, &token, &1), 1);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 1);
+    assert_eq!(client.get_solvency(&payment_token).liabilities, 1);
+}
+
+// ── Additional rounding mode tests ────────────────────────────
+
+#[test]
+fn compute_share_round_half_even() {
+    let env = Env::default();
+    let client = make_client(&env);
+    // 5 * 5000 / 10000 = 2.5 -> 2; 3 * 5000 / 10000 = 1.5 -> 2
+    assert_eq!(client.compute_share(&5, &5_000, &RoundingMode::RoundHalfEven), 2);
+    assert_eq!(client.compute_share(&3, &5_000, &RoundingMode::RoundHalfEven), 2);
+    // Above half still rounds up: 3 * 3333 / 10000 = 0.9999 -> 1
+    assert_eq!(client.compute_share(&3, &3_333, &RoundingMode::RoundHalfEven), 1);
+    assert_eq!(client.compute_share(&5, &5_000, &RoundingMode::RoundHalfUp), 3);
+}
+
+#[test]
+fn compute_share_ceiling() {
+    let env = Env::default();
+    let client = make_client(&env);
+    // 100 * 1 / 10000 = 0.01 -> 1
+    assert_eq!(client.compute_share(&100, &1, &RoundingMode::Ceiling), 1);
+    assert_eq!(client.compute_share(&1_000, &2_500, &RoundingMode::Ceiling), 250);
+    assert_eq!(client.compute_share(&7, &10_000, &RoundingMode::Ceiling), 7);
+}
+
+#[test]
+fn compute_share_largest_remainder_truncates_single_share() {
+    let env = Env::default();
+    let client = make_client(&env);
+    assert_eq!(client.compute_share(&3, &3_333, &RoundingMode::LargestRemainder), 0);
+}
+
+#[test]
+fn allocate_batch_sums_exactly_to_amount() {
+    let env = Env::default();
+    let client = make_client(&env);
+    let holders = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let mut shares = Vec::new(&env);
+    shares.push_back((holders[0].clone(), 3_333u32));
+    shares.push_back((holders[1].clone(), 3_333u32));
+    shares.push_back((holders[2].clone(), 3_334u32));
+
+    // Exact: 33.33, 33.33, 33.34 -> floors 33, 33, 33; the last has the largest remainder
+    let result = client.allocate_batch(&100, &shares);
+    assert_eq!(result.total_distributed, 100);
+    assert_eq!(result.payouts.get(0).unwrap(), (holders[0].clone(), 33));
+    assert_eq!(result.payouts.get(1).unwrap(), (holders[1].clone(), 33));
+    assert_eq!(result.payouts.get(2).unwrap(), (holders[2].clone(), 34));
+}
+
+#[test]
+fn allocate_batch_breaks_ties_by_input_order() {
+    let env = Env::default();
+    let client = make_client(&env);
+    let holders = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let mut shares = Vec::new(&env);
+    for holder in holders.iter() {
+        shares.push_back((holder.clone(), 2_500u32));
+    }
+
+    // 75% of 10 = 7.5 -> 7 distributable; floors 2, 2, 2 and one unit to the first
+    let result = client.allocate_batch(&10, &shares);
+    assert_eq!(result.total_distributed, 7);
+    assert_eq!(result.payouts.get(0).unwrap().1, 3);
+    assert_eq!(result.payouts.get(1).unwrap().1, 2);
+    assert_eq!(result.payouts.get(2).unwrap().1, 2);
+}
+
+#[test]
+fn allocate_batch_gives_leftover_units_to_largest_remainders() {
+    let env = Env::default();
+    let client = make_client(&env);
+    let mut shares = Vec::new(&env);
+    for share in [1_000u32, 2_900, 1_500, 2_700, 1_900] {
+        shares.push_back((Address::generate(&env), share));
+    }
+
+    // Exact: 1.0, 2.9, 1.5, 2.7, 1.9 -> floors 1, 2, 1, 2, 1 and 3 units left over
+    let result = client.allocate_batch(&10, &shares);
+    assert_eq!(result.total_distributed, 10);
+    for (i, expected) in [1, 3, 1, 3, 2].iter().enumerate() {
+        assert_eq!(result.payouts.get(i as u32).unwrap().1, *expected);
+    }
+}
+
+#[test]
+fn allocate_batch_rejects_shares_over_10000() {
+    let env = Env::default();
+    let client = make_client(&env);
+    let mut shares = Vec::new(&env);
+    shares.push_back((Address::generate(&env), 6_000u32));
+    shares.push_back((Address::generate(&env), 5_000u32));
+    let r = client.try_allocate_batch(&100, &shares);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::InvalidShareBps)));
+}
+
+#[test]
+fn simulate_distribution_largest_remainder_has_no_dust() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_rounding_mode(&issuer, &token, &RoundingMode::LargestRemainder);
+    let mut shares = Vec::new(&env);
+    for _ in 0..3 {
+        shares.push_back((Address::generate(&env), 3_333u32));
+    }
+    shares.push_back((Address::generate(&env), 1u32));
+
+    let result = client.simulate_distribution(&issuer, &token, &10, &shares);
+    assert_eq!(result.total_distributed, 10);
+    let sum: i128 = result.payouts.iter().map(|(_, a)| a).sum();
+    assert_eq!(sum, 10);
+}
+
+// ── Share precision tests ─────────────────────────────────────
+
+#[test]
+fn ppm_precision_allows_shares_below_one_bps() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_share_precision(&issuer, &token, &SharePrecision::Ppm);
+    let holder = Address::generate(&env);
+    client.set_holder_share_units(&issuer, &token, &holder, &50);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000_000, &1);
+
+    assert_eq!(client.get_holder_share_units(&token, &holder), 50);
+    // The bps view truncates 0.005% to 0
+    assert_eq!(client.get_holder_share(&token, &holder), 0);
+    assert_eq!(client.claim(&holder, &token, &0), 50);
+}
+
+#[test]
+fn bps_api_is_a_view_over_finer_precision() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_share_precision(&issuer, &token, &SharePrecision::Fixed18);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &2_500);
+
+    assert_eq!(client.get_holder_share_units(&token, &holder), 250_000_000_000_000_000);
+    assert_eq!(client.get_holder_share(&token, &holder), 2_500);
+    assert_eq!(client.get_total_allocated_bps(&token), 2_500);
+    assert_eq!(client.get_unallocated_bps(&token), 7_500);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    assert_eq!(client.get_holder_share_at(&token, &holder, &0), 2_500);
+    assert_eq!(client.claim(&holder, &token, &0), 250);
+}
+
+#[test]
+fn bps_offerings_keep_shares_and_checkpoints_in_bps() {
+    let (env, client, issuer, token, _payment_token, contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &1_500);
+
+    let stored: Option<u32> = env.as_contract(&contract_id, || {
+        env.storage().persistent().get(&crate::DataKey::HolderShare(token.clone(), holder.clone()))
+    });
+    assert_eq!(stored, Some(1_500));
+    assert_eq!(
+        client.get_share_checkpoints(&token, &holder),
+        vec![&env, ShareCheckpoint { from_period_index: 0, share_bps: 1_500 }]
+    );
+}
+
+#[test]
+fn finer_precision_checkpoints_and_events_carry_units() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    client.set_share_precision(&issuer, &token, &SharePrecision::Ppm);
+    let holder = Address::generate(&env);
+    client.set_holder_share_units(&issuer, &token, &holder, &123_456);
+
+    let share_set = (
+        contract_id,
+        (symbol_short!("share_set"), issuer.clone(), token.clone()).into_val(&env),
+        (holder.clone(), 1_234_u32, 123_456_i128).into_val(&env),
+    );
+    assert!(env.events().all().contains(share_set));
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000_000, &1);
+    client.set_holder_share_units(&issuer, &token, &holder, &50);
+    assert_eq!(
+        client.get_share_checkpoints_units(&token, &holder),
+        vec![
+            &env,
+            ShareCheckpointUnits { from_period_index: 0, share: 123_456 },
+            ShareCheckpointUnits { from_period_index: 1, share: 50 },
+        ]
+    );
+    assert_eq!(
+        client.get_share_checkpoints(&token, &holder),
+        vec![
+            &env,
+            ShareCheckpoint { from_period_index: 0, share_bps: 1_234 },
+            ShareCheckpoint { from_period_index: 1, share_bps: 0 },
+        ]
+    );
+}
+
+#[test]
+fn share_units_are_capped_at_the_offering_share() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_share_precision(&issuer, &token, &SharePrecision::Ppm);
+    client.set_holder_share_units(&issuer, &token, &Address::generate(&env), &600_000);
+
+    let r = client.try_set_holder_share_units(&issuer, &token, &Address::generate(&env), &400_001);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ShareAllocationExceeded)));
+    let r = client.try_set_holder_share_units(&issuer, &token, &Address::generate(&env), &-1);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::InvalidShareBps)));
+}
+
+#[test]
+fn share_precision_is_fixed_once_holders_exist() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    assert_eq!(client.get_share_precision(&token), SharePrecision::Bps);
+    client.set_holder_share(&issuer, &token, &Address::generate(&env), &1_000);
+
+    let r = client.try_set_share_precision(&issuer, &token, &SharePrecision::Ppm);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+}
+
+#[test]
+fn accumulator_mode_requires_bps_precision() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_share_precision(&issuer, &token, &SharePrecision::Ppm);
+    let r = client.try_set_accumulator_mode(&issuer, &token, &true);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+}
+
+#[test]
+fn compute_and_simulate_in_share_units() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    assert_eq!(
+        client.compute_share_units(&1_000_000, &1, &SharePrecision::Ppm, &RoundingMode::Truncation),
+        1
+    );
+    assert_eq!(client.compute_share(&1_000_000, &1, &RoundingMode::Truncation), 100);
+
+    client.set_share_precision(&issuer, &token, &SharePrecision::Ppm);
+    let holder = Address::generate(&env);
+    let mut shares = Vec::new(&env);
+    shares.push_back((holder.clone(), 125_i128));
+    let result = client.simulate_distribution_units(&issuer, &token, &1_000_000, &shares);
+    assert_eq!(result.payouts.get(0).unwrap(), (holder, 125));
+}
+
+// ── Wide mul-div overflow tests ───────────────────────────────
+
+#[test]
+fn compute_share_is_exact_near_i128_max() {
+    let env = Env::default();
+    let client = make_client(&env);
+    assert_eq!(
+        client.compute_share(&i128::MAX, &3_333, &RoundingMode::Truncation),
+        56_708_056_447_374_394_936_171_378_328_504_172_438
+    );
+    assert_eq!(
+        client.compute_share(&i128::MAX, &3_333, &RoundingMode::RoundHalfUp),
+        56_708_056_447_374_394_936_171_378_328_504_172_439
+    );
+    assert_eq!(client.compute_share(&i128::MAX, &10_000, &RoundingMode::Ceiling), i128::MAX);
+    assert_eq!(client.compute_share(&i128::MIN, &10_000, &RoundingMode::Truncation), i128::MIN);
+}
+
+#[test]
+fn claim_pays_out_a_deposit_of_i128_max() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (payment_token, pt_admin) = create_payment_token(&env);
+    client.register_offering(&issuer, &token, &10_000, &payment_token);
+    mint_tokens(&env, &payment_token, &pt_admin, &issuer, &i128::MAX);
+
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &i128::MAX, &1);
+
+    let expected = i128::MAX / 2;
+    assert_eq!(client.get_claimable(&token, &holder), expected);
+    assert_eq!(client.claim(&holder, &token, &0), expected);
+    assert_eq!(balance(&env, &payment_token, &holder), expected);
+}
+
+#[test]
+fn calculate_distribution_handles_i128_max_inputs() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    let caller = Address::generate(&env);
+    let holder = Address::generate(&env);
+    client.register_offering(&issuer, &token, &5_000, &token);
+
+    assert_eq!(client.calculate_total_distributable(&issuer, &token, &i128::MAX), i128::MAX / 2);
+    let payout = client.calculate_distribution(
+        &caller,
+        &issuer,
+        &token,
+        &i128::MAX,
+        &i128::MAX,
+        &(i128::MAX / 2),
+        &holder,
+    );
+    // (MAX / 2) * (MAX / 2) / MAX, truncated
+    assert_eq!(payout, i128::MAX / 4);
+}
+
+#[test]
+fn calculate_total_distributable_overflow_is_a_typed_error() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    client.set_admin(&admin);
+    client.set_testnet_mode(&true);
+    // Testnet mode allows revenue shares above 100%
+    client.register_offering(&issuer, &token, &20_000, &token);
+
+    let r = client.try_calculate_total_distributable(&issuer, &token, &i128::MAX);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ArithmeticOverflow)));
+}
+
+// ── TWAB entitlement tests ────────────────────────────────────
+
+#[test]
+fn twab_share_bought_just_before_deposit_earns_little() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.set_twab_config(&issuer, &token, &true);
+    client.set_holder_share(&issuer, &token, &early, &5_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_900);
+    client.set_holder_share(&issuer, &token, &late, &5_000);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+
+    assert_eq!(client.get_period_twab_window(&token, &1), Some((1_000, 2_000)));
+    assert_eq!(client.get_twab_share(&token, &early, &1), Some(5_000));
+    assert_eq!(client.get_twab_share(&token, &late, &1), Some(500));
+    assert_eq!(client.claim(&early, &token, &0), 5_000);
+    assert_eq!(client.claim(&late, &token, &0), 500);
+}
+
+#[test]
+fn twab_share_change_inside_window_is_averaged() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+    client.set_twab_config(&issuer, &token, &true);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 750);
+    client.set_holder_share(&issuer, &token, &holder, &2_000);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+
+    // (10000 * 750 + 2000 * 250) / 1000 = 8000 bps, then 2000 bps all window
+    assert_eq!(client.get_twab_share(&token, &holder, &1), Some(8_000));
+    assert_eq!(client.get_period_twab_window(&token, &2), Some((1_000, 2_000)));
+    assert_eq!(client.get_claimable(&token, &holder), 800 + 200);
+    assert_eq!(client.get_twab_observations(&token, &holder).len(), 2);
+}
+
+#[test]
+fn snapshot_deposit_leaves_the_twab_window_unchanged() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_snapshot_config(&issuer, &token, &true);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_000;
+        li.sequence_number = 100;
+    });
+    client.set_twab_config(&issuer, &token, &true);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    client.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &1_000, &1, &100);
+    assert_eq!(client.get_period_twab_window(&token, &1), None);
+
+    // The next TWAB window still starts where TWAB was enabled
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    assert_eq!(client.get_period_twab_window(&token, &2), Some((1_000, 2_000)));
+}
+
+#[test]
+fn twab_conflicts_with_accumulator_and_dust_settlement() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_twab_config(&issuer, &token, &true);
+    assert!(client.get_twab_config(&token));
+    let r = client.try_set_accumulator_mode(&issuer, &token, &true);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let r = client.try_settle_period_dust(&issuer, &token, &1);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+
+    client.set_twab_config(&issuer, &token, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    assert_eq!(client.get_period_twab_window(&token, &2), None);
+
+    let (_env, client2, issuer2, token2, _payment_token, _contract_id) = claim_setup();
+    client2.set_accumulator_mode(&issuer2, &token2, &true);
+    let r = client2.try_set_twab_config(&issuer2, &token2, &true);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+}
+
+// ── Release schedule tests ────────────────────────────────────
+
+#[test]
+fn linear_release_is_claimable_in_parts() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_release_schedule(&issuer, &token, &0, &1_000);
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_250);
+    assert_eq!(client.get_claimable(&token, &holder), 1_250);
+    assert_eq!(client.claim(&holder, &token, &0), 1_250);
+    assert_eq!(client.get_partial_claim(&token, &holder, &1), 1_250);
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_600);
+    assert_eq!(client.claim(&holder, &token, &0), 1_750);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 1);
+
+    env.ledger().with_mut(|li| li.timestamp = 11_000);
+    assert_eq!(client.claim(&holder, &token, &0), 2_000);
+    assert_eq!(client.get_partial_claim(&token, &holder, &1), 0);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 0);
+    let r = client.try_claim(&holder, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NoPendingClaims)));
+}
+
+#[test]
+fn cliff_holds_back_revenue_then_releases_linearly() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.set_release_schedule(&issuer, &token, &500, &1_000);
+    assert_eq!(
+        client.get_release_schedule(&token),
+        ReleaseSchedule { cliff_secs: 500, linear_secs: 1_000 }
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    env.ledger().with_mut(|li| li.timestamp = 1_400);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+
+    let r = client.try_claim(&holder, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ClaimDelayNotElapsed)));
+
+    // Period 1 is 100s past its cliff, period 2 still inside it
+    env.ledger().with_mut(|li| li.timestamp = 1_600);
+    assert_eq!(client.claim(&holder, &token, &0), 100);
+
+    // Period 1 fully released, period 2 700s past its cliff
+    env.ledger().with_mut(|li| li.timestamp = 2_600);
+    assert_eq!(client.claim(&holder, &token, &0), 900 + 700);
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 2u64]);
+}
+
+#[test]
+fn release_schedule_is_fixed_per_period_at_deposit() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.set_release_schedule(&issuer, &token, &0, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    client.set_release_schedule(&issuer, &token, &0, &0);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &3);
+
+    assert_eq!(client.get_period_release(&token, &1), ReleaseSchedule::default());
+    assert_eq!(
+        client.get_period_release(&token, &2),
+        ReleaseSchedule { cliff_secs: 0, linear_secs: 1_000 }
+    );
+    assert_eq!(client.get_period_release(&token, &3), ReleaseSchedule::default());
+
+    // Only period 2 releases over time, whatever the schedule is now
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert_eq!(client.claim(&holder, &token, &0), 1_000 + 500 + 1_000);
+    client.set_release_schedule(&issuer, &token, &5_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.claim(&holder, &token, &0), 500);
+}
+
+#[test]
+fn release_schedule_conflicts_with_accumulator_mode() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_release_schedule(&issuer, &token, &0, &100);
+    let r = client.try_set_accumulator_mode(&issuer, &token, &true);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+    client.set_release_schedule(&issuer, &token, &0, &0);
+    client.set_accumulator_mode(&issuer, &token, &true);
+
+    let r = client.try_set_release_schedule(&issuer, &token, &60, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DistributionModeConflict)));
+}
+
+// ── claim_periods tests ───────────────────────────────────────
+
+#[test]
+fn claim_periods_out_of_order_then_claim_skips_them() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &2_000, &2);
+    client.deposit_revenue(&issuer, &token, &payment_token, &3_000, &3);
+
+    assert_eq!(client.claim_periods(&holder, &token, &vec![&env, 3_u64]), 1_500);
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 1_u64, 2_u64]);
+    assert_eq!(client.get_claimable(&token, &holder), 1_500);
+
+    let r = client.try_claim_periods(&holder, &token, &vec![&env, 3_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::PeriodNotClaimable)));
+    let r = client.try_claim_periods(&holder, &token, &vec![&env, 2_u64, 2_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::PeriodNotClaimable)));
+    let r = client.try_claim_periods(&holder, &token, &vec![&env, 9_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::InvalidPeriodId)));
+
+    // The claim index moves over period 3 as well
+    assert_eq!(client.claim(&holder, &token, &1), 500);
+    assert_eq!(client.claim(&holder, &token, &0), 1_000);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 0);
+    let r = client.try_claim_periods(&holder, &token, &vec![&env, 1_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::PeriodNotClaimable)));
+}
+
+#[test]
+fn pending_periods_report_claimable_at() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.set_claim_delay(&issuer, &token, &100);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    env.ledger().with_mut(|li| li.timestamp = 1_050);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+
+    assert_eq!(client.get_pending_periods(&token, &holder), vec![&env, 1_u64, 2_u64]);
+    let pending = client.get_pending_periods_detailed(&token, &holder);
+    assert_eq!(pending.get(0).unwrap(), PendingPeriod { period_id: 1, claimable_at: 1_100 });
+    assert_eq!(pending.get(1).unwrap(), PendingPeriod { period_id: 2, claimable_at: 1_150 });
+
+    env.ledger().with_mut(|li| li.timestamp = 1_120);
+    let r = client.try_claim_periods(&holder, &token, &vec![&env, 1_u64, 2_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ClaimDelayNotElapsed)));
+    assert_eq!(client.claim_periods(&holder, &token, &vec![&env, 1_u64]), 1_000);
+}
+
+#[test]
+fn pending_periods_detailed_waits_for_the_first_linear_release() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_release_schedule(&issuer, &token, &100, &1_000);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &200, &1);
+
+    // 100 is entitled over 1000s: one unit is released every 10s after the cliff
+    let pending = client.get_pending_periods_detailed(&token, &holder);
+    assert_eq!(pending.get(0).unwrap(), PendingPeriod { period_id: 1, claimable_at: 1_110 });
+    env.ledger().with_mut(|li| li.timestamp = 1_109);
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    env.ledger().with_mut(|li| li.timestamp = 1_110);
+    assert_eq!(client.get_claimable(&token, &holder), 1);
+
+    // After a partial claim, the next unit is due 10s later
+    env.ledger().with_mut(|li| li.timestamp = 1_355);
+    assert_eq!(client.claim(&holder, &token, &0), 25);
+    let pending = client.get_pending_periods_detailed(&token, &holder);
+    assert_eq!(pending.get(0).unwrap(), PendingPeriod { period_id: 1, claimable_at: 1_360 });
+}
+
+#[test]
+fn claim_periods_pays_released_part_of_a_releasing_period() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.set_release_schedule(&issuer, &token, &0, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(client.claim_periods(&holder, &token, &vec![&env, 2_u64]), 200);
+    let r = client.try_claim_periods(&holder, &token, &vec![&env, 2_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::ClaimDelayNotElapsed)));
+
+    env.ledger().with_mut(|li| li.timestamp = 900);
+    assert_eq!(client.claim_periods(&holder, &token, &vec![&env, 1_u64, 2_u64]), 900 + 300);
+    assert_eq!(client.get_partial_claim(&token, &holder, &2), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_400);
+    assert_eq!(client.claim(&holder, &token, &0), 100 + 500);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 0);
+}
+
+// ── Whitelist enforcement tests ───────────────────────────────
+
+#[test]
+fn whitelist_changes_require_issuer_or_admin() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let outsider = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    let r = client.try_whitelist_add(&outsider, &token, &outsider);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+    let r = client.try_whitelist_add(&issuer, &Address::generate(&env), &investor);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::OfferingNotFound)));
+
+    client.whitelist_add(&issuer, &token, &investor);
+    let r = client.try_whitelist_remove(&outsider, &token, &investor);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+    client.whitelist_remove(&admin, &token, &investor);
+    assert!(!client.is_whitelisted(&token, &investor));
+}
+
+#[test]
+fn claim_enforces_whitelist_with_blacklist_precedence() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_admin(&Address::generate(&env));
+    let listed = Address::generate(&env);
+    let unlisted = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &listed, &5_000);
+    client.set_holder_share(&issuer, &token, &unlisted, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.whitelist_add(&issuer, &token, &listed);
+
+    assert_eq!(client.get_claimable(&token, &unlisted), 0);
+    let r = client.try_claim(&unlisted, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotWhitelisted)));
+    let r = client.try_claim_periods(&unlisted, &token, &vec![&env, 1_u64]);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotWhitelisted)));
+
+    client.blacklist_add(&issuer, &token, &unlisted);
+    let r = client.try_claim(&unlisted, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderBlacklisted)));
+
+    client.blacklist_add(&issuer, &token, &listed);
+    assert_eq!(client.get_claimable(&token, &listed), 0);
+    let r = client.try_claim(&listed, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderBlacklisted)));
+    client.blacklist_remove(&issuer, &token, &listed);
+    assert_eq!(client.claim(&listed, &token, &0), 500);
+
+    // An emptied whitelist no longer restricts claims
+    client.whitelist_remove(&issuer, &token, &listed);
+    client.blacklist_remove(&issuer, &token, &unlisted);
+    assert_eq!(client.claim(&unlisted, &token, &0), 500);
+}
+
+#[test]
+fn push_distribution_skips_and_reports_holders_off_whitelist() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &1_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.whitelist_add(&issuer, &token, &holder_a);
+
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.paid, vec![&env, (holder_a, 10_000)]);
+    assert_eq!(result.skipped_not_whitelisted, vec![&env, holder_b.clone()]);
+    assert_eq!(balance(&env, &payment_token, &holder_b), 0);
+}
+
+// ── Blacklist policy tests ────────────────────────────────────
+
+#[test]
+fn blacklisted_revenue_is_escrowed_until_removal() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.blacklist_add(&issuer, &token, &holder);
+
+    assert_eq!(client.get_blacklist_policy(&token), BlacklistPolicy::Escrow);
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &token, &holder, &0), 500);
+    assert_eq!(client.get_escrowed_revenue(&token, &holder), 500);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 0);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    client.blacklist_remove(&issuer, &token, &holder);
+    assert_eq!(balance(&env, &payment_token, &holder), 500);
+    assert_eq!(client.get_escrowed_revenue(&token, &holder), 0);
+    assert_eq!(client.claim(&holder, &token, &0), 500);
+}
+
+#[test]
+fn escrowed_revenue_is_claimable_once_the_entry_expires() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.blacklist_add_with_reason(&issuer, &token, &holder, &0, &None, &Some(1_500));
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &token, &holder, &0), 500);
+
+    let r = client.try_claim_escrow(&holder, &token);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderBlacklisted)));
+
+    // Nothing calls blacklist_remove; the holder takes the escrow themselves
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert_eq!(client.claim_escrow(&holder, &token), 500);
+    assert_eq!(balance(&env, &payment_token, &holder), 500);
+    assert_eq!(client.get_escrowed_revenue(&token, &holder), 0);
+    assert_eq!(client.get_offering_liabilities(&token).total_claimed, 500);
+    let r = client.try_claim_escrow(&holder, &token);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NoPendingClaims)));
+}
+
+#[test]
+fn blacklisted_revenue_is_redistributed_pro_rata_to_other_holders() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup_with_share(10_000);
+    client.set_admin(&Address::generate(&env));
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let holder_c = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &2_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &6_000);
+    client.set_holder_share(&issuer, &token, &holder_c, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &u64::MAX);
+    client.blacklist_add(&issuer, &token, &holder_b);
+    client.set_blacklist_policy(&issuer, &token, &BlacklistPolicy::Redistribute);
+
+    // B's 600 per period goes back into that period, to A and C in their 2000:2000 ratio
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &token, &holder_b, &0), 1_200);
+    for period_id in [1_u64, u64::MAX] {
+        assert!(env.events().all().contains((
+            contract_id.clone(),
+            (symbol_short!("bl_redist"), token.clone(), holder_b.clone()).into_val(&env),
+            (period_id, 600_i128).into_val(&env),
+        )));
+    }
+    assert_eq!(client.get_pending_periods(&token, &holder_a), vec![&env, 1_u64, u64::MAX]);
+    assert_eq!(client.get_period_count(&token), 2);
+    assert_eq!(client.get_dust_ledger(&token).rolled, 0);
+    assert_eq!(client.get_claimable(&token, &holder_a), 2 * (200 + 300));
+    assert_eq!(client.claim(&holder_a, &token, &0), 2 * (200 + 300));
+    assert_eq!(client.claim(&holder_c, &token, &0), 2 * (200 + 300));
+
+    // B has no part in the redistribution, even once removed
+    client.blacklist_remove(&issuer, &token, &holder_b);
+    assert_eq!(client.get_pending_periods(&token, &holder_b).len(), 0);
+    let r = client.try_claim(&holder_b, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NoPendingClaims)));
+    assert_eq!(client.get_offering_liabilities(&token).outstanding, 0);
+}
+
+#[test]
+fn redistributed_revenue_goes_to_holders_not_yet_paid_from_the_period() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_admin(&Address::generate(&env));
+    client.set_blacklist_policy(&issuer, &token, &BlacklistPolicy::Redistribute);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    let holder_c = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &2_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &6_000);
+    client.set_holder_share(&issuer, &token, &holder_c, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    assert_eq!(client.claim(&holder_a, &token, &0), 200);
+    client.blacklist_add(&issuer, &token, &holder_b);
+
+    // A was already paid from period 1, so C takes all of B's 600
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &token, &holder_b, &0), 600);
+    assert_eq!(client.get_claimable(&token, &holder_a), 0);
+    assert_eq!(client.claim(&holder_c, &token, &0), 200 + 600);
+
+    // Nobody is left to be paid from period 2 once C has claimed it
+    client.blacklist_remove(&issuer, &token, &holder_b);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    assert_eq!(client.claim(&holder_a, &token, &0), 200);
+    assert_eq!(client.claim(&holder_c, &token, &0), 200);
+    client.blacklist_add(&issuer, &token, &holder_b);
+    let r = client.try_settle_blacklisted_revenue(&issuer, &token, &holder_b, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NoRemainingHolders)));
+    assert_eq!(client.get_offering_liabilities(&token).outstanding, 600);
+}
+
+#[test]
+#[should_panic(expected = "contract is paused")]
+fn settle_blacklisted_revenue_blocked_while_paused() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.blacklist_add(&issuer, &token, &holder);
+    client.pause_admin(&admin);
+    client.settle_blacklisted_revenue(&issuer, &token, &holder, &0);
+}
+
+#[test]
+fn redistribution_needs_another_holder_and_works_in_accumulator_mode() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_admin(&Address::generate(&env));
+    client.set_accumulator_mode(&issuer, &token, &true);
+    client.set_blacklist_policy(&issuer, &token, &BlacklistPolicy::Redistribute);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_b, &6_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.blacklist_add(&issuer, &token, &holder_b);
+
+    let r = client.try_settle_blacklisted_revenue(&issuer, &token, &holder_b, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NoRemainingHolders)));
+
+    client.set_holder_share(&issuer, &token, &holder_a, &2_000);
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &token, &holder_b, &0), 600);
+    assert_eq!(client.get_claimable(&token, &holder_a), 600);
+
+    // B earns again from later deposits only
+    client.blacklist_remove(&issuer, &token, &holder_b);
+    assert_eq!(client.get_claimable(&token, &holder_b), 0);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    assert_eq!(client.get_claimable(&token, &holder_b), 600);
+    assert_eq!(client.claim(&holder_a, &token, &0), 600 + 200);
+}
+
+#[test]
+fn blacklisted_revenue_returns_to_issuer() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup_with_share(10_000);
+    client.set_admin(&Address::generate(&env));
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_holder_share(&issuer, &token, &other, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.set_blacklist_policy(&issuer, &token, &BlacklistPolicy::ReturnToIssuer);
+    assert_eq!(client.get_blacklist_policy(&token), BlacklistPolicy::ReturnToIssuer);
+
+    let r = client.try_settle_blacklisted_revenue(&issuer, &token, &holder, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderNotBlacklisted)));
+    client.blacklist_add(&issuer, &token, &holder);
+    let r = client.try_settle_blacklisted_revenue(&other, &token, &holder, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+
+    let before = balance(&env, &payment_token, &issuer);
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &token, &holder, &0), 500);
+    assert_eq!(balance(&env, &payment_token, &issuer), before + 500);
+    assert_eq!(client.get_offering_liabilities(&token).total_swept, 500);
+    assert_eq!(client.get_offering_liabilities(&token).outstanding, 500);
+    let r = client.try_settle_blacklisted_revenue(&issuer, &token, &holder, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NoPendingClaims)));
+
+    // Removal does not give back revenue that was returned
+    client.blacklist_remove(&issuer, &token, &holder);
+    assert_eq!(balance(&env, &payment_token, &holder), 0);
+    assert_eq!(client.claim(&other, &token, &0), 500);
+}
+
+// ── Blacklist record tests ────────────────────────────────────
+
+#[test]
+fn blacklist_records_carry_reason_evidence_and_authority() {
+    let (env, client, issuer, token, _payment_token, contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let evidence = BytesN::from_array(&env, &[7; 32]);
+
+    client.blacklist_add_with_reason(&issuer, &token, &a, &3, &Some(evidence.clone()), &None);
+    let digest = client.get_blacklist_digest(&token);
+    assert_eq!(digest.count, 1);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("bl_add"), token.clone(), issuer.clone()).into_val(&env),
+                (a.clone(), 3_u32, Some(evidence.clone()), None::<u64>, digest).into_val(&env),
+            ),
+        ]
+    );
+    client.blacklist_add_with_reason(&admin, &token, &b, &5, &None, &Some(2_000));
+    client.blacklist_add(&issuer, &token, &c);
+
+    let (page, next) = client.get_blacklist(&token, &0, &2);
+    assert_eq!(next, Some(2));
+    assert_eq!(
+        page.get(0).unwrap(),
+        BlacklistRecord {
+            investor: a.clone(),
+            reason: 3,
+            evidence_hash: evidence,
+            authority: issuer.clone(),
+            added_at: 1_000,
+            expires_at: None,
+        }
+    );
+    assert_eq!(page.get(1).unwrap().authority, admin);
+    assert_eq!(page.get(1).unwrap().evidence_hash, BytesN::from_array(&env, &[0; 32]));
+    let (page, next) = client.get_blacklist(&token, &2, &2);
+    assert_eq!(next, None);
+    assert_eq!(page.get(0).unwrap().investor, c);
+    assert_eq!(page.get(0).unwrap().reason, 0);
+
+    client.blacklist_remove(&issuer, &token, &a);
+    let digest = client.get_blacklist_digest(&token);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id,
+                (symbol_short!("bl_rem"), token.clone(), issuer.clone()).into_val(&env),
+                (a, 3_u32, digest).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_blacklist(&token, &0, &0).0.len(), 2);
+}
+
+#[test]
+fn blacklist_entry_without_a_record_reads_as_a_default_record() {
+    let (env, client, issuer, token, _payment_token, contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    client.blacklist_add(&issuer, &token, &investor);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .remove(&crate::DistributionKey::BlacklistRecord(token.clone(), investor.clone()));
+    });
+
+    assert!(client.is_blacklisted(&token, &investor));
+    let (page, next) = client.get_blacklist(&token, &0, &0);
+    assert_eq!(next, None);
+    assert_eq!(
+        page,
+        vec![
+            &env,
+            BlacklistRecord {
+                investor: investor.clone(),
+                reason: 0,
+                evidence_hash: BytesN::from_array(&env, &[0; 32]),
+                authority: contract_id.clone(),
+                added_at: 0,
+                expires_at: None,
+            },
+        ]
+    );
+    client.blacklist_remove(&issuer, &token, &investor);
+    assert!(!client.is_blacklisted(&token, &investor));
+}
+
+#[test]
+fn expired_blacklist_entry_no_longer_blocks_claims() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+
+    let r = client.try_blacklist_add_with_reason(&issuer, &token, &holder, &1, &None, &Some(1_000));
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::InvalidExpiry)));
+    client.blacklist_add_with_reason(&issuer, &token, &holder, &1, &None, &Some(1_500));
+    assert!(client.is_blacklisted(&token, &holder));
+    let r = client.try_claim(&holder, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderBlacklisted)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert!(!client.is_blacklisted(&token, &holder));
+    assert_eq!(client.claim(&holder, &token, &0), 500);
+    // The expired record stays listed until it is removed
+    assert_eq!(client.get_blacklist(&token, &0, &0).0.get(0).unwrap().expires_at, Some(1_500));
+}
+
+// ── Blacklist/whitelist storage tests ─────────────────────────
+
+#[test]
+fn list_pages_keep_insertion_order_across_removals_and_digest_ignores_order() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    client.whitelist_add(&issuer, &token, &a);
+    client.whitelist_add(&issuer, &token, &b);
+    client.whitelist_add(&issuer, &token, &c);
+    client.whitelist_add(&issuer, &token, &c);
+    assert_eq!(client.get_whitelist_digest(&token).count, 3);
+
+    let (page, next) = client.get_whitelist_page(&token, &0, &2);
+    assert_eq!(page, vec![&env, a.clone(), b.clone()]);
+    assert_eq!(next, Some(2));
+    // Removing an entry already paged past does not shift the rest under the cursor
+    client.whitelist_remove(&issuer, &token, &a);
+    let (page, next) = client.get_whitelist_page(&token, &2, &2);
+    assert_eq!(page, vec![&env, c.clone()]);
+    assert_eq!(next, None);
+    client.whitelist_add(&issuer, &token, &a);
+    let (page, next) = client.get_whitelist_page(&token, &0, &1);
+    assert_eq!(page.len(), 0);
+    assert_eq!(next, Some(1));
+    let (page, next) = client.get_whitelist_page(&token, &1, &0);
+    assert_eq!(page, vec![&env, b.clone(), c.clone(), a.clone()]);
+    assert_eq!(next, None);
+    assert_eq!(client.get_whitelist_digest(&token).count, 3);
+    client.whitelist_remove(&issuer, &token, &a);
+
+    // The blacklist of another offering holding the same addresses in another order
+    let other_token = Address::generate(&env);
+    client.register_offering(&issuer, &other_token, &10_000, &other_token);
+    client.blacklist_add(&issuer, &other_token, &c);
+    client.blacklist_add(&issuer, &other_token, &b);
+    assert_eq!(client.get_blacklist_digest(&other_token), client.get_whitelist_digest(&token));
+
+    client.whitelist_remove(&issuer, &token, &b);
+    client.whitelist_remove(&issuer, &token, &c);
+    assert_eq!(
+        client.get_whitelist_digest(&token),
+        ListDigest { count: 0, hash: BytesN::from_array(&env, &[0; 32]) }
+    );
+    assert!(!client.is_whitelist_enabled(&token));
+}
+
+#[test]
+fn legacy_lists_count_until_migrated_in_pages() {
+    let (env, client, issuer, token, _payment_token, contract_id) = claim_setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let d = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        let blacklist = soroban_sdk::Map::from_array(
+            &env,
+            [(a.clone(), true), (b.clone(), true), (c.clone(), true)],
+        );
+        storage.set(&crate::DataKey::Blacklist(token.clone()), &blacklist);
+        storage.set(
+            &crate::DataKey::BlacklistOrder(token.clone()),
+            &vec![&env, c.clone(), a.clone(), b.clone()],
+        );
+        let whitelist = soroban_sdk::Map::from_array(&env, [(d.clone(), true)]);
+        storage.set(&crate::DataKey::Whitelist(token.clone()), &whitelist);
+    });
+
+    assert!(client.is_blacklisted(&token, &a));
+    assert!(client.is_whitelist_enabled(&token));
+    assert!(client.is_whitelisted(&token, &d));
+    assert_eq!(client.get_blacklist_digest(&token).count, 0);
+    client.blacklist_remove(&issuer, &token, &b);
+    assert!(!client.is_blacklisted(&token, &b));
+
+    assert!(!client.migrate_legacy_lists(&issuer, &token, &1));
+    assert_eq!(client.get_blacklist_page(&token, &0, &0).0, vec![&env, c.clone()]);
+    assert!(client.migrate_legacy_lists(&issuer, &token, &0));
+    assert!(env.events().all().contains((
+        contract_id.clone(),
+        (symbol_short!("lst_migr"), token.clone(), issuer.clone()).into_val(&env),
+        (2_u32, true).into_val(&env),
+    )));
+    assert_eq!(client.get_blacklist_page(&token, &0, &0).0, vec![&env, c.clone(), a.clone()]);
+    assert_eq!(client.get_blacklist(&token, &1, &1).0.get(0).unwrap().reason, 0);
+    assert_eq!(client.get_blacklist_digest(&token).count, 2);
+    assert_eq!(client.get_whitelist_page(&token, &0, &0).0, vec![&env, d.clone()]);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&crate::DataKey::Blacklist(token.clone())));
+        assert!(!storage.has(&crate::DataKey::BlacklistOrder(token.clone())));
+        assert!(!storage.has(&crate::DataKey::Whitelist(token.clone())));
+    });
+
+    client.whitelist_remove(&issuer, &token, &d);
+    assert!(!client.is_whitelist_enabled(&token));
+    let r = client.try_migrate_legacy_lists(&Address::generate(&env), &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+}
+
+#[test]
+fn report_revenue_event_carries_blacklist_digest() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    client.blacklist_add(&issuer, &token, &Address::generate(&env));
+    client.report_revenue(&issuer, &token, &payment_token, &1_000, &1, &false);
+
+    let digest = client.get_blacklist_digest(&token);
+    assert_eq!(digest.count, 1);
+    let rev_rep = (
+        contract_id,
+        (symbol_short!("rev_rep"), issuer, token).into_val(&env),
+        (1_000_i128, 1_u64, digest).into_val(&env),
+    );
+    assert!(env.events().all().contains(rev_rep));
+}
+
+// ── Platform sanctions tests ──────────────────────────────────
+
+#[test]
+fn sanctions_managed_by_admin_or_compliance() {
+    let (env, client, _issuer, _token, _payment_token, contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let compliance = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    let r = client.try_sanction_add(&compliance, &investor);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+
+    client.set_compliance(&Some(compliance.clone()));
+    assert_eq!(client.get_compliance(), Some(compliance.clone()));
+    client.sanction_add(&compliance, &investor);
+    let digest = client.get_sanctions_digest();
+    assert_eq!(digest.count, 1);
+    let snc_add = (
+        contract_id,
+        (symbol_short!("snc_add"), compliance.clone()).into_val(&env),
+        (investor.clone(), digest).into_val(&env),
+    );
+    assert!(env.events().all().contains(snc_add));
+    assert!(client.is_sanctioned(&investor));
+    assert_eq!(client.get_sanctions_page(&0, &0), (vec![&env, investor.clone()], None));
+
+    client.set_compliance(&None);
+    let r = client.try_sanction_remove(&compliance, &investor);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NotAuthorized)));
+    client.sanction_remove(&admin, &investor);
+    assert!(!client.is_sanctioned(&investor));
+    assert_eq!(client.get_sanctions_digest().count, 0);
+}
+
+#[test]
+fn sanctions_block_claims_on_every_offering_before_offering_lists() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let other_token = Address::generate(&env);
+    client.register_offering(&issuer, &other_token, &10_000, &payment_token);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_holder_share(&issuer, &other_token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.deposit_revenue(&issuer, &other_token, &payment_token, &2_000, &1);
+    client.whitelist_add(&issuer, &token, &holder);
+    client.blacklist_add(&issuer, &other_token, &holder);
+
+    client.sanction_add(&admin, &holder);
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    let r = client.try_claim(&holder, &token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderSanctioned)));
+    let r = client.try_claim(&holder, &other_token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderSanctioned)));
+
+    client.sanction_remove(&admin, &holder);
+    assert_eq!(client.claim(&holder, &token, &0), 500);
+    let r = client.try_claim(&holder, &other_token, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderBlacklisted)));
+}
+
+#[test]
+fn sanctioned_revenue_follows_offering_policy_and_escrow_is_released_on_removal() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let other_token = Address::generate(&env);
+    client.register_offering(&issuer, &other_token, &10_000, &payment_token);
+    client.set_blacklist_policy(&issuer, &other_token, &BlacklistPolicy::ReturnToIssuer);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_holder_share(&issuer, &other_token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.deposit_revenue(&issuer, &other_token, &payment_token, &2_000, &1);
+
+    let r = client.try_settle_blacklisted_revenue(&issuer, &token, &holder, &0);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderNotBlacklisted)));
+    client.sanction_add(&admin, &holder);
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &token, &holder, &0), 500);
+    assert_eq!(client.get_escrowed_revenue(&token, &holder), 500);
+    let issuer_before = balance(&env, &payment_token, &issuer);
+    assert_eq!(client.settle_blacklisted_revenue(&issuer, &other_token, &holder, &0), 1_000);
+    assert_eq!(balance(&env, &payment_token, &issuer), issuer_before + 1_000);
+
+    client.sanction_remove(&admin, &holder);
+    assert_eq!(balance(&env, &payment_token, &holder), 500);
+    assert_eq!(client.get_escrowed_revenue(&token, &holder), 0);
+    let r = client.try_claim_escrow(&holder, &token);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::NoPendingClaims)));
+}
+
+#[test]
+fn sanctioned_holder_gets_no_share_and_is_skipped_by_push() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let holder_a = Address::generate(&env);
+    let holder_b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder_a, &1_000);
+    client.set_holder_share(&issuer, &token, &holder_b, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.sanction_add(&admin, &holder_b);
+
+    let r = client.try_set_holder_share(&issuer, &token, &holder_b, &3_000);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::HolderSanctioned)));
+
+    let result = client.push_distribution(&issuer, &token, &None, &0, &0, &0);
+    assert_eq!(result.paid, vec![&env, (holder_a, 10_000)]);
+    assert_eq!(result.skipped_sanctioned, vec![&env, holder_b.clone()]);
+    assert_eq!(balance(&env, &payment_token, &holder_b), 0);
+
+    // Clearing a sanctioned holder's share is still allowed
+    client.set_holder_share(&issuer, &token, &holder_b, &0);
+    assert_eq!(client.get_holder_share(&token, &holder_b), 0);
+}
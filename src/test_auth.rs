@@ -1,5 +1,5 @@
 #![cfg(test)]
-use soroban_sdk::{testutils::Address as _, Address, Env, String as SdkString, Vec};
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SdkString};
 
 use crate::{RevoraRevenueShare, RevoraRevenueShareClient, RoundingMode};
 
@@ -11,7 +11,7 @@ fn make_client(env: &Env) -> RevoraRevenueShareClient<'_> {
 fn init_admin_safety(env: &Env, client: &RevoraRevenueShareClient) -> (Address, Address) {
     let admin = Address::generate(env);
     let safety = Address::generate(env);
-    client.initialize(&admin, &Some(safety.clone()));
+    client.initialize(&admin, &Some(safety.clone()), &None::<bool>);
     (admin, safety)
 }
 
@@ -248,7 +248,7 @@ fn blacklist_add_wrong_caller_no_mutation() {
         .try_blacklist_add(&attacker, &token, &investor)
         .is_err());
     assert!(!client.is_blacklisted(&token, &investor));
-    let bl: Vec<Address> = client.get_blacklist(&token);
+    let (bl, _) = client.get_blacklist_page(&token, &0, &0);
     assert_eq!(bl.len(), 0);
 }
 
@@ -269,6 +269,27 @@ fn blacklist_remove_wrong_caller_no_mutation() {
     assert!(client.is_blacklisted(&token, &investor));
 }
 
+#[test]
+fn set_compliance_missing_auth_no_mutation() {
+    let env = Env::default();
+    let client = make_client(&env);
+    let (_admin, _safety) = init_admin_safety(&env, &client);
+    let compliance = Address::generate(&env);
+    assert!(client.try_set_compliance(&Some(compliance)).is_err());
+    assert!(client.get_compliance().is_none());
+}
+
+#[test]
+fn sanction_add_wrong_caller_no_mutation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (_admin, safety) = init_admin_safety(&env, &client);
+    let investor = Address::generate(&env);
+    assert!(client.try_sanction_add(&safety, &investor).is_err());
+    assert!(!client.is_sanctioned(&investor));
+}
+
 #[test]
 fn cross_offering_confusion_wrong_issuer_no_mutation() {
     let env = Env::default();
@@ -53,11 +53,12 @@
 #![cfg(test)]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, testutils::Address as _,
-    Address, Env,
+    contract, contracterror, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env,
 };
 
-use crate::{RevoraRevenueShare, RevoraRevenueShareClient};
+use crate::{RevoraError, RevoraRevenueShare, RevoraRevenueShareClient};
 
 // ===========================================================================
 // Mock Token Registry Contract
@@ -164,6 +165,41 @@ impl MockPriceOracle {
     }
 }
 
+// ===========================================================================
+// Mock Snapshot Token Contract
+// ===========================================================================
+// Simulates an offering token that can report historical balances, as required
+// for periods deposited with `deposit_revenue_with_snapshot` (see `SnapshotToken`).
+
+/// Mock snapshot token: balances and supply are recorded per ledger by the test.
+#[contract]
+pub struct MockSnapshotToken;
+
+#[contractimpl]
+impl MockSnapshotToken {
+    /// Record `holder`'s balance as of `ledger`.
+    pub fn set_balance(env: Env, holder: Address, ledger: u64, amount: i128) {
+        let key = (symbol_short!("bal"), holder, ledger);
+        env.storage().persistent().set(&key, &amount);
+    }
+
+    /// Record the total supply as of `ledger`.
+    pub fn set_total_supply(env: Env, ledger: u64, amount: i128) {
+        let key = (symbol_short!("supply"), ledger);
+        env.storage().persistent().set(&key, &amount);
+    }
+
+    pub fn balance_at(env: Env, holder: Address, ledger: u64) -> i128 {
+        let key = (symbol_short!("bal"), holder, ledger);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    pub fn total_supply_at(env: Env, ledger: u64) -> i128 {
+        let key = (symbol_short!("supply"), ledger);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+}
+
 // ===========================================================================
 // Tests: Cross-Contract Interaction Patterns
 // ===========================================================================
@@ -589,3 +625,130 @@ fn cross_contract_stress_revora_with_registry_and_oracle() {
     assert_eq!(registry.get_token_count(), 10);
     assert_eq!(revora.get_offering_count(&issuer), 10);
 }
+
+// ── Pattern 7: Snapshot-Bound Entitlement ────────────────────
+
+/// Revora with an offering whose token is a `MockSnapshotToken`, snapshot
+/// distribution enabled, a funded issuer and the ledger at sequence 1000.
+fn snapshot_setup(
+) -> (Env, RevoraRevenueShareClient<'static>, MockSnapshotTokenClient<'static>, Address, Address) {
+    snapshot_setup_with_share(10_000)
+}
+
+fn snapshot_setup_with_share(
+    revenue_share_bps: u32,
+) -> (Env, RevoraRevenueShareClient<'static>, MockSnapshotTokenClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+
+    let revora_id = env.register_contract(None, RevoraRevenueShare);
+    let snapshot_id = env.register_contract(None, MockSnapshotToken);
+    let revora = RevoraRevenueShareClient::new(&env, &revora_id);
+    let snapshot = MockSnapshotTokenClient::new(&env, &snapshot_id);
+
+    let issuer = Address::generate(&env);
+    let payment_token = env.register_stellar_asset_contract(Address::generate(&env));
+    token::StellarAssetClient::new(&env, &payment_token).mint(&issuer, &1_000_000);
+
+    revora.register_offering(&issuer, &snapshot_id, &revenue_share_bps, &payment_token);
+    revora.set_snapshot_config(&issuer, &snapshot_id, &true);
+
+    (env, revora, snapshot, issuer, payment_token)
+}
+
+#[test]
+fn cross_contract_snapshot_period_pays_pro_rata_to_balances() {
+    let (env, revora, snapshot, issuer, payment_token) = snapshot_setup();
+    let token = snapshot.address.clone();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    snapshot.set_balance(&a, &100, &30);
+    snapshot.set_balance(&b, &100, &70);
+    snapshot.set_total_supply(&100, &100);
+
+    // No HolderShare is set: the snapshot alone decides entitlement
+    revora.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &1_000, &1, &100);
+    assert_eq!(revora.get_period_snapshot(&token, &1), Some(100));
+    assert_eq!(revora.get_claimable(&token, &a), 300);
+
+    assert_eq!(revora.claim(&a, &token, &0), 300);
+    assert_eq!(revora.claim(&b, &token, &0), 700);
+    let payments = token::Client::new(&env, &payment_token);
+    assert_eq!(payments.balance(&a), 300);
+    assert_eq!(payments.balance(&b), 700);
+}
+
+#[test]
+fn cross_contract_snapshot_each_period_reads_its_own_ledger() {
+    let (env, revora, snapshot, issuer, payment_token) = snapshot_setup();
+    let token = snapshot.address.clone();
+    let holder = Address::generate(&env);
+    snapshot.set_balance(&holder, &100, &50);
+    snapshot.set_total_supply(&100, &100);
+    snapshot.set_balance(&holder, &200, &25);
+    snapshot.set_total_supply(&200, &100);
+
+    revora.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &1_000, &1, &100);
+    revora.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &1_000, &2, &200);
+
+    // Later balance changes do not affect recorded snapshots
+    snapshot.set_balance(&holder, &300, &100);
+    assert_eq!(revora.claim(&holder, &token, &0), 750);
+}
+
+#[test]
+fn cross_contract_snapshot_holder_without_balance_gets_nothing() {
+    let (env, revora, snapshot, issuer, payment_token) = snapshot_setup();
+    let token = snapshot.address.clone();
+    let holder = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    snapshot.set_balance(&holder, &100, &10);
+    snapshot.set_total_supply(&100, &40);
+
+    revora.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &1_000, &1, &100);
+    assert_eq!(revora.get_claimable(&token, &outsider), 0);
+    assert_eq!(revora.claim(&outsider, &token, &0), 0);
+    assert_eq!(revora.claim(&holder, &token, &0), 250);
+}
+
+#[test]
+fn cross_contract_snapshot_period_pays_only_the_revenue_share() {
+    let (env, revora, snapshot, issuer, payment_token) = snapshot_setup_with_share(5_000);
+    let token = snapshot.address.clone();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    snapshot.set_balance(&a, &100, &30);
+    snapshot.set_balance(&b, &100, &70);
+    snapshot.set_total_supply(&100, &100);
+
+    // The whole supply shares 50% of the deposit
+    revora.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &1_000, &1, &100);
+    assert_eq!(revora.get_claimable(&token, &a), 150);
+    assert_eq!(revora.claim(&a, &token, &0), 150);
+    assert_eq!(revora.claim(&b, &token, &0), 350);
+    let payments = token::Client::new(&env, &payment_token);
+    assert_eq!(payments.balance(&revora.address), 500);
+}
+
+#[test]
+fn cross_contract_snapshot_period_settles_dust_of_claimed_balances() {
+    let (env, revora, snapshot, issuer, payment_token) = snapshot_setup_with_share(5_000);
+    let token = snapshot.address.clone();
+    let holders = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for holder in holders.iter() {
+        snapshot.set_balance(holder, &100, &1);
+    }
+    snapshot.set_total_supply(&100, &3);
+
+    revora.deposit_revenue_with_snapshot(&issuer, &token, &payment_token, &1_000, &1, &100);
+    for holder in holders.iter() {
+        assert_eq!(revora.claim(holder, &token, &0), 166);
+    }
+
+    // 500 is owed to the claimed balances; 498 was paid
+    assert_eq!(revora.settle_period_dust(&issuer, &token, &1), 2);
+    assert_eq!(revora.get_period_dust(&token, &1), Some(2));
+    let r = revora.try_settle_period_dust(&issuer, &token, &1);
+    assert!(matches!(r.err().unwrap(), Ok(RevoraError::DustAlreadySettled)));
+}